@@ -0,0 +1,21 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(0) var<uniform> scanlines: f32;
+
+// How far the corners of the screen bulge outwards.
+const CURVATURE: f32 = 0.08;
+const SCANLINE_DARKNESS: f32 = 0.25;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let centered = mesh.uv * 2.0 - 1.0;
+    // Barrel distortion, the screen's edges bend inwards away from the center lines.
+    let curved = centered * (1.0 + CURVATURE * dot(centered.yx, centered.yx));
+    if any(abs(curved) > vec2(1.0)) {
+        return vec4(0.0, 0.0, 0.0, 1.0);
+    }
+
+    let scanline = 0.5 + 0.5 * sin(mesh.uv.y * scanlines * 6.2831853);
+    let vignette = smoothstep(0.6, 1.0, length(curved) / sqrt(2.0));
+    return vec4(0.0, 0.0, 0.0, max(SCANLINE_DARKNESS * scanline, vignette));
+}
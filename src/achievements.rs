@@ -0,0 +1,182 @@
+use crate::camera::hud_layer;
+use crate::fonts::GameFont;
+use crate::storage::{SaveStorage, saves};
+use crate::{BodyPart, Custom, Score};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bincode::{Decode, Encode, config};
+use std::io;
+use std::time::Duration;
+
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+const SURVIVAL_TIME: Duration = Duration::from_secs(60);
+
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    FirstApple,
+    Length10,
+    Survive60Seconds,
+    BeatHighScore,
+}
+
+impl Achievement {
+    fn title(self) -> &'static str {
+        match self {
+            Achievement::FirstApple => "First apple",
+            Achievement::Length10 => "Length 10",
+            Achievement::Survive60Seconds => "Survive 60 seconds",
+            Achievement::BeatHighScore => "Beat the high score",
+        }
+    }
+}
+
+/// Achievements unlocked over all runs, in the order they were unlocked.
+#[derive(Resource, Encode, Decode, Debug, Default)]
+pub struct Unlocked(Vec<Achievement>);
+
+/// What the current run is measured against to unlock achievements, along with the counters
+/// summarized on the game over screen.
+#[derive(Resource)]
+pub struct RunStats {
+    started: Duration,
+    high_score: u32,
+    apples: u32,
+    max_length: usize,
+    wraps: u32,
+}
+
+impl RunStats {
+    pub fn new(started: Duration, high_score: u32) -> Self {
+        Self {
+            started,
+            high_score,
+            apples: 0,
+            max_length: 0,
+            wraps: 0,
+        }
+    }
+
+    /// Lines describing the run up to `now`, in game time.
+    pub fn summary(&self, now: Duration) -> [String; 3] {
+        let duration = now.saturating_sub(self.started).as_secs_f32();
+        let per_apple = match self.apples {
+            0 => String::from("-"),
+            apples => format!("{:.1}s", duration / apples as f32),
+        };
+        [
+            format!("Apples: {}    Longest: {}", self.apples, self.max_length),
+            format!(
+                "Time: {}:{:02}    Per apple: {per_apple}",
+                duration as u32 / 60,
+                duration as u32 % 60
+            ),
+            format!("Wraps: {}", self.wraps),
+        ]
+    }
+
+    /// Whether `score` beats the high score from before this run.
+    pub fn is_new_high_score(&self, score: u32) -> bool {
+        score > self.high_score
+    }
+}
+
+#[derive(Component)]
+pub struct AchievementToast(Timer);
+
+pub fn record_run_apple(mut run_stats: ResMut<RunStats>) {
+    run_stats.apples += 1;
+}
+
+pub fn record_run_length(mut run_stats: ResMut<RunStats>, body_parts: Query<(), With<BodyPart>>) {
+    run_stats.max_length = run_stats.max_length.max(body_parts.iter().count());
+}
+
+pub fn record_run_wrap(mut run_stats: ResMut<RunStats>) {
+    run_stats.wraps += 1;
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn check_achievements(
+    mut commands: Commands,
+    mut unlocked: ResMut<Unlocked>,
+    run_stats: Res<RunStats>,
+    score: Query<&Score>,
+    body_parts: Query<(), With<BodyPart>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
+    time: Res<Time>,
+) {
+    let score = score.single().0;
+    let reached = [
+        (Achievement::FirstApple, score > 0),
+        (Achievement::Length10, body_parts.iter().count() >= 10),
+        (
+            Achievement::Survive60Seconds,
+            time.elapsed() - run_stats.started >= SURVIVAL_TIME,
+        ),
+        (
+            Achievement::BeatHighScore,
+            run_stats.high_score > 0 && run_stats.is_new_high_score(score),
+        ),
+    ];
+
+    let mut toasts = 0;
+    for (achievement, _) in reached.into_iter().filter(|(_, reached)| *reached) {
+        if unlocked.0.contains(&achievement) {
+            continue;
+        }
+        unlocked.0.push(achievement);
+        let resolution = &window.single().resolution;
+        commands.spawn((
+            Custom,
+            AchievementToast(Timer::new(TOAST_DURATION, TimerMode::Once)),
+            hud_layer(),
+            Text2d::new(format!("Achievement unlocked: {}", achievement.title())),
+            TextColor(Color::srgb(1.0, 0.8, 0.2)),
+            TextFont {
+                font: game_font.handle.clone(),
+                font_size: 30.0,
+                ..default()
+            },
+            Transform::from_xyz(
+                0.0,
+                resolution.height() / -2.0 + 70.0 + 40.0 * toasts as f32,
+                1.0,
+            ),
+        ));
+        toasts += 1;
+    }
+    if toasts > 0 {
+        save_achievements(&unlocked).expect("could not save achievements");
+    }
+}
+
+pub fn expire_achievement_toasts(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut AchievementToast)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast) in query.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn load_achievements() -> io::Result<Unlocked> {
+    let Some(content) = saves().read("achievements")? else {
+        return Ok(Unlocked::default());
+    };
+    Ok(bincode::decode_from_slice(&content, config::standard())
+        .map(|(unlocked, _)| unlocked)
+        .unwrap_or_else(|err| {
+            warn!("could not decode achievements, starting over: {err}");
+            Unlocked::default()
+        }))
+}
+
+fn save_achievements(unlocked: &Unlocked) -> io::Result<()> {
+    let encoded = bincode::encode_to_vec(unlocked, config::standard())
+        .expect("failed to encode achievements");
+    saves().write("achievements", &encoded)
+}
@@ -0,0 +1,181 @@
+use crate::board::Board;
+use crate::camera::hud_layer;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::{Apple, Custom, Head};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bincode::{Decode, Encode};
+
+/// Distance of the arrow from the edge of the window.
+const ARROW_MARGIN: f32 = 40.0;
+const HINT_COLOR: Color = Color::srgb(1.0, 1.0, 0.5);
+/// How long the highlight of a new apple takes to shrink onto it.
+const FLASH_SECONDS: f32 = 0.6;
+/// Size of the highlight of a new apple when it appears, in cells.
+const FLASH_SIZE: f32 = 3.0;
+
+/// Help to find the apple quickly, for players who have a hard time spotting it.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AppleHint {
+    #[default]
+    Off,
+    /// An arrow at the edge of the window points towards the apple, next to its distance.
+    Arrow,
+    /// A highlight around each new apple shrinks onto it.
+    Flash,
+}
+
+impl AppleHint {
+    const ALL: [AppleHint; 3] = [AppleHint::Off, AppleHint::Arrow, AppleHint::Flash];
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|h| *h == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+#[derive(Component)]
+pub struct AppleArrow;
+
+#[derive(Component)]
+pub struct AppleArrowHead;
+
+#[derive(Component)]
+pub struct AppleArrowText;
+
+#[derive(Component)]
+pub struct AppleFlash(Timer);
+
+pub fn spawn_apple_arrow(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    color_materials: &mut Assets<ColorMaterial>,
+    font: Handle<Font>,
+) {
+    commands
+        .spawn((
+            Custom,
+            AppleArrow,
+            hud_layer(),
+            Transform::default(),
+            Visibility::Hidden,
+        ))
+        .with_children(|arrow| {
+            arrow.spawn((
+                AppleArrowHead,
+                hud_layer(),
+                Mesh2d(meshes.add(Triangle2d::new(
+                    Vec2::new(15.0, 0.0),
+                    Vec2::new(-10.0, 12.0),
+                    Vec2::new(-10.0, -12.0),
+                ))),
+                MeshMaterial2d(color_materials.add(HINT_COLOR)),
+            ));
+            arrow.spawn((
+                AppleArrowText,
+                hud_layer(),
+                Text2d::default(),
+                TextColor(HINT_COLOR),
+                TextFont {
+                    font,
+                    font_size: 20.0,
+                    ..default()
+                },
+                Transform::default(),
+            ));
+        });
+}
+
+/// Places the arrow where the direction from the head towards the apple meets the edge of the
+/// window, taking the shorter way through the edges of the board if the walls wrap.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_apple_arrow(
+    mut arrow: Query<(&mut Transform, &mut Visibility), With<AppleArrow>>,
+    mut arrow_head: Query<&mut Transform, (With<AppleArrowHead>, Without<AppleArrow>)>,
+    mut arrow_text: Query<
+        (&mut Text2d, &mut Transform),
+        (
+            With<AppleArrowText>,
+            Without<AppleArrow>,
+            Without<AppleArrowHead>,
+        ),
+    >,
+    head: Query<&Cell, With<Head>>,
+    apples: Query<&Cell, With<Apple>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+) {
+    let Ok((mut transform, mut visibility)) = arrow.get_single_mut() else {
+        return;
+    };
+    let (Ok(head), Some(apple)) = (
+        head.get_single(),
+        apples
+            .iter()
+            .next()
+            .filter(|_| settings.apple_hint == AppleHint::Arrow),
+    ) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    let offset = board.offset(*head, *apple);
+    let direction = offset.as_vec2().normalize_or_zero();
+    if direction == Vec2::ZERO {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+
+    let (Ok(window), Ok(mut arrow_head), Ok((mut text, mut text_transform))) = (
+        window.get_single(),
+        arrow_head.get_single_mut(),
+        arrow_text.get_single_mut(),
+    ) else {
+        return;
+    };
+    let half_size = window.resolution.size() / 2.0 - ARROW_MARGIN;
+    let reach = (half_size / direction.abs()).min_element();
+    transform.translation = (direction * reach).extend(0.0);
+    arrow_head.rotation = Quat::from_rotation_z(direction.to_angle());
+    text.0 = format!("{} cells", board.distance(*head, *apple));
+    // Inwards from the arrow, so the text stays within the window.
+    text_transform.translation = (-direction * 45.0).extend(0.0);
+    *visibility = Visibility::Inherited;
+}
+
+pub fn flash_new_apples(
+    mut commands: Commands,
+    apples: Query<&Cell, Added<Apple>>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+) {
+    if settings.apple_hint != AppleHint::Flash {
+        return;
+    }
+    for cell in apples.iter() {
+        commands.spawn((
+            Custom,
+            AppleFlash(Timer::from_seconds(FLASH_SECONDS, TimerMode::Once)),
+            Sprite::from_color(HINT_COLOR.with_alpha(0.6), Vec2::splat(board.cell_size)),
+            Transform::from_translation(board.cell_to_world(*cell).extend(-0.9))
+                .with_scale(Vec3::splat(FLASH_SIZE)),
+        ));
+    }
+}
+
+pub fn shrink_apple_flashes(
+    mut commands: Commands,
+    mut flashes: Query<(Entity, &mut AppleFlash, &mut Sprite, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut sprite, mut transform) in flashes.iter_mut() {
+        if flash.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let progress = flash.0.fraction();
+        transform.scale = Vec3::splat(FLASH_SIZE.lerp(1.0, progress));
+        sprite.color.set_alpha(0.6 * (1.0 - progress));
+    }
+}
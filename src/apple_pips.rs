@@ -0,0 +1,90 @@
+use crate::AppleEatenEvent;
+use bevy::prelude::*;
+use bincode::{Decode, Encode};
+
+/// Apples a full row of pips stands for, the row starts over with the next apple.
+const PIPS_PER_ROW: u32 = 10;
+const PIP_SIZE: f32 = 24.0;
+const EMPTY_PIP_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.2);
+
+/// How the score is shown in the top left corner.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreStyle {
+    #[default]
+    Text,
+    /// A row of apple icons filling up with every apple eaten.
+    Pips,
+    Both,
+}
+
+impl ScoreStyle {
+    const ALL: [ScoreStyle; 3] = [ScoreStyle::Text, ScoreStyle::Pips, ScoreStyle::Both];
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|s| *s == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    pub fn shows_text(self) -> bool {
+        self != ScoreStyle::Pips
+    }
+
+    pub fn shows_pips(self) -> bool {
+        self != ScoreStyle::Text
+    }
+}
+
+/// Row of pips, holding the apples eaten this run.
+#[derive(Component)]
+pub struct ApplePips(u32);
+
+#[derive(Component)]
+pub struct ApplePip;
+
+/// Adds the empty row below the score text to the score column.
+pub fn spawn_apple_pips(parent: &mut ChildBuilder, apple_texture: Handle<Image>) {
+    parent
+        .spawn((
+            ApplePips(0),
+            Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(4.0),
+                ..default()
+            },
+        ))
+        .with_children(|row| {
+            for _ in 0..PIPS_PER_ROW {
+                row.spawn((
+                    ApplePip,
+                    ImageNode::new(apple_texture.clone()).with_color(EMPTY_PIP_COLOR),
+                    Node {
+                        width: Val::Px(PIP_SIZE),
+                        height: Val::Px(PIP_SIZE),
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+/// Fills one pip per eaten apple, a completed row stays full until the next apple starts over.
+pub fn fill_apple_pips(
+    mut apple_eaten_event: EventReader<AppleEatenEvent>,
+    mut row: Query<(&mut ApplePips, &Children)>,
+    mut pips: Query<&mut ImageNode, With<ApplePip>>,
+) {
+    let Ok((mut eaten, children)) = row.get_single_mut() else {
+        return;
+    };
+    eaten.0 += apple_eaten_event.read().count() as u32;
+    let filled = (eaten.0 - 1) % PIPS_PER_ROW + 1;
+    for (index, child) in children.iter().enumerate() {
+        if let Ok(mut pip) = pips.get_mut(*child) {
+            pip.color = if (index as u32) < filled {
+                Color::WHITE
+            } else {
+                EMPTY_PIP_COLOR
+            };
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use bevy::utils::HashSet;
+use bincode::{Decode, Encode};
+use rand::prelude::IndexedRandom;
+use rand::rngs::StdRng;
+
+/// Way of picking the cell of a new apple among the free ones.
+///
+/// Candidates must be considered in the fixed order of [`Board::cells`], never in the order of
+/// the unordered `occupied` set. So the choice only depends on the random numbers drawn, and a
+/// seeded [`GameRng`](crate::GameRng) spawns the same apples in every run and on every platform.
+pub trait ApplePlacement {
+    /// Picks a free cell, or `None` if the whole board is occupied.
+    fn choose(&self, board: &Board, occupied: &HashSet<Cell>, rng: &mut StdRng) -> Option<Cell>;
+}
+
+fn free_cells(board: &Board, occupied: &HashSet<Cell>) -> Vec<Cell> {
+    board
+        .cells()
+        .filter(|cell| !occupied.contains(cell))
+        .collect()
+}
+
+/// Picks a cell among the free ones, each with the given weight. The weights must be positive.
+fn choose_weighted(
+    board: &Board,
+    occupied: &HashSet<Cell>,
+    rng: &mut StdRng,
+    weight: impl Fn(Cell) -> u32,
+) -> Option<Cell> {
+    free_cells(board, occupied)
+        .choose_weighted(rng, |cell| weight(*cell))
+        .ok()
+        .copied()
+}
+
+/// Every free cell is equally likely.
+pub struct UniformPlacement;
+
+impl ApplePlacement for UniformPlacement {
+    fn choose(&self, board: &Board, occupied: &HashSet<Cell>, rng: &mut StdRng) -> Option<Cell> {
+        free_cells(board, occupied).choose(rng).copied()
+    }
+}
+
+/// Free cells are the more likely the farther they are from the closest occupied cell, so
+/// apples tend to appear in the open rather than right next to the snake.
+pub struct DistanceWeightedPlacement;
+
+impl ApplePlacement for DistanceWeightedPlacement {
+    fn choose(&self, board: &Board, occupied: &HashSet<Cell>, rng: &mut StdRng) -> Option<Cell> {
+        choose_weighted(board, occupied, rng, |cell| {
+            occupied
+                .iter()
+                .map(|other| board.distance(cell, *other))
+                .min()
+                .unwrap_or(1)
+        })
+    }
+}
+
+/// Free cells are the more likely the closer they are to the edge of the board, with the cells
+/// on the edge being `half_extent + 1` times as likely as the center cell.
+pub struct EdgeBiasedPlacement;
+
+impl ApplePlacement for EdgeBiasedPlacement {
+    fn choose(&self, board: &Board, occupied: &HashSet<Cell>, rng: &mut StdRng) -> Option<Cell> {
+        choose_weighted(board, occupied, rng, |cell| {
+            1 + cell.x.unsigned_abs().max(cell.y.unsigned_abs())
+        })
+    }
+}
+
+/// Placement of the regular apple as chosen in the settings.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementMode {
+    #[default]
+    Uniform,
+    AwayFromSnake,
+    NearEdges,
+}
+
+impl PlacementMode {
+    pub const ALL: [PlacementMode; 3] = [
+        PlacementMode::Uniform,
+        PlacementMode::AwayFromSnake,
+        PlacementMode::NearEdges,
+    ];
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|m| *m == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    pub fn strategy(self) -> &'static dyn ApplePlacement {
+        match self {
+            PlacementMode::Uniform => &UniformPlacement,
+            PlacementMode::AwayFromSnake => &DistanceWeightedPlacement,
+            PlacementMode::NearEdges => &EdgeBiasedPlacement,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn never_places_on_the_snake() {
+        let board = Board::new(5, 1.0);
+        // Everything but a handful of cells is taken by the snake.
+        let free = [Cell::new(-2, -2), Cell::new(0, 1), Cell::new(2, 0)];
+        let occupied = board
+            .cells()
+            .filter(|cell| !free.contains(cell))
+            .collect::<HashSet<_>>();
+        let mut rng = GameRng(StdRng::seed_from_u64(7));
+
+        for mode in PlacementMode::ALL {
+            for _ in 0..1000 {
+                let cell = mode
+                    .strategy()
+                    .choose(&board, &occupied, &mut rng.0)
+                    .expect("expected a free cell");
+                assert!(!occupied.contains(&cell), "{mode:?} placed on the snake");
+            }
+        }
+    }
+
+    /// Average of `measure` over many apples placed by `placement`.
+    fn average(
+        placement: &dyn ApplePlacement,
+        board: &Board,
+        occupied: &HashSet<Cell>,
+        measure: impl Fn(Cell) -> u32,
+    ) -> f32 {
+        let mut rng = StdRng::seed_from_u64(3);
+        let total = (0..2000)
+            .map(|_| measure(placement.choose(board, occupied, &mut rng).unwrap()))
+            .sum::<u32>();
+        total as f32 / 2000.0
+    }
+
+    #[test]
+    fn uniform_placement_picks_among_the_free_cells_in_board_order() {
+        let board = Board::new(7, 1.0);
+        let occupied = HashSet::from_iter([Cell::new(0, 0), Cell::new(-1, 0)]);
+        let mut first = StdRng::seed_from_u64(11);
+        let mut second = StdRng::seed_from_u64(11);
+
+        for _ in 0..100 {
+            assert_eq!(
+                UniformPlacement.choose(&board, &occupied, &mut first),
+                free_cells(&board, &occupied).choose(&mut second).copied()
+            );
+        }
+    }
+
+    #[test]
+    fn away_from_snake_prefers_cells_far_from_it() {
+        let board = Board::new(9, 1.0);
+        let occupied = HashSet::from_iter([Cell::new(0, 0), Cell::new(-1, 0)]);
+        let distance = |cell| board.distance(cell, Cell::new(0, 0));
+
+        let uniform = average(&UniformPlacement, &board, &occupied, distance);
+        let away = average(&DistanceWeightedPlacement, &board, &occupied, distance);
+        assert!(away > uniform + 0.5, "{away} vs {uniform}");
+    }
+
+    #[test]
+    fn near_edges_prefers_the_edge() {
+        let board = Board::new(9, 1.0);
+        let occupied = HashSet::new();
+        let on_edge = |cell: Cell| u32::from(cell.x.abs() == 4 || cell.y.abs() == 4);
+
+        let uniform = average(&UniformPlacement, &board, &occupied, on_edge);
+        let near_edges = average(&EdgeBiasedPlacement, &board, &occupied, on_edge);
+        assert!(near_edges > uniform + 0.05, "{near_edges} vs {uniform}");
+    }
+
+    #[test]
+    fn finds_no_cell_on_a_full_board() {
+        let board = Board::new(3, 1.0);
+        let occupied = board.cells().collect::<HashSet<_>>();
+        let mut rng = GameRng(StdRng::seed_from_u64(7));
+
+        assert_eq!(UniformPlacement.choose(&board, &occupied, &mut rng.0), None);
+    }
+}
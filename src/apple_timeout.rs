@@ -0,0 +1,52 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::shrink_apple::ShrinkApple;
+use crate::{Apple, BodyPart, GameRng, Obstacle};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use std::time::Duration;
+
+/// Time an apple has been lying at its current cell.
+#[derive(Component, Default)]
+pub struct AppleAge(Duration);
+
+/// Moves apples that weren't eaten within the configured timeout to another free cell.
+#[allow(clippy::type_complexity)]
+pub fn relocate_stale_apples(
+    mut apples: Query<(&mut AppleAge, &mut Cell, &mut Transform), With<Apple>>,
+    blocked: Query<
+        &Cell,
+        (
+            Or<(With<BodyPart>, With<ShrinkApple>, With<Obstacle>)>,
+            Without<Apple>,
+        ),
+    >,
+    mut rng: ResMut<GameRng>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    let Some(timeout) = settings.apple_timeout else {
+        return;
+    };
+    for (mut age, mut cell, mut transform) in apples.iter_mut() {
+        age.0 += time.delta();
+        if age.0 < Duration::from_secs_f32(timeout) {
+            continue;
+        }
+        age.0 = Duration::ZERO;
+
+        // The current cell counts as occupied too, so the apple actually moves.
+        let mut occupied = blocked.iter().copied().collect::<HashSet<_>>();
+        occupied.insert(*cell);
+        if let Some(new_cell) = settings
+            .apple_placement
+            .strategy()
+            .choose(&board, &occupied, &mut rng.0)
+        {
+            *cell = new_cell;
+            transform.translation = board.cell_to_world(new_cell).extend(-1.0);
+        }
+    }
+}
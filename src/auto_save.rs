@@ -0,0 +1,48 @@
+use crate::settings::Settings;
+use crate::state_dump::describe_state;
+use crate::storage::{SaveStorage, saves};
+use bevy::prelude::*;
+use bevy::utils::Instant;
+use std::time::Duration;
+
+/// Name of the save the running game is written to, `assets/saves/auto_save.txt` natively.
+const AUTO_SAVE_NAME: &str = "auto_save.txt";
+/// Saves taking longer than this are reported, as they would hold up a frame noticeably.
+const SLOW_SAVE: Duration = Duration::from_millis(4);
+
+/// Time until the running game is saved next, restarted with every run.
+#[derive(Resource, Default)]
+pub struct AutoSave(Timer);
+
+/// Writes the state of the running game every `auto_save` seconds, in the format of the state
+/// dump, so a crash loses at most that much of the run.
+///
+/// The storage replaces the previous save in one step, a crash while saving keeps the last one.
+pub fn auto_save(world: &mut World) {
+    let Some(interval) = world.resource::<Settings>().auto_save else {
+        return;
+    };
+    let interval = Duration::from_secs_f32(interval);
+    let delta = world.resource::<Time>().delta();
+    let mut auto_save = world.resource_mut::<AutoSave>();
+    // Follows changes of the interval during a run.
+    if auto_save.0.duration() != interval {
+        auto_save.0 = Timer::new(interval, TimerMode::Repeating);
+    }
+    if !auto_save.0.tick(delta).just_finished() {
+        return;
+    }
+
+    let start = Instant::now();
+    let result = describe_state(world).and_then(|state| {
+        saves()
+            .write(AUTO_SAVE_NAME, state.as_bytes())
+            .map_err(|err| err.to_string())
+    });
+    let took = start.elapsed();
+    match result {
+        Err(err) => warn!("could not auto-save the game: {err}"),
+        Ok(()) if took > SLOW_SAVE => warn!("auto-saving the game took {took:?}"),
+        Ok(()) => debug!("auto-saved the game in {took:?}"),
+    }
+}
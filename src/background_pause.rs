@@ -0,0 +1,52 @@
+use crate::GameState;
+use crate::settings::Settings;
+use bevy::prelude::*;
+use bevy::window::{AppLifecycle, WindowFocused, WindowOccluded};
+
+/// Pauses a running game once the player can no longer see or control it, so the snake
+/// doesn't die unattended.
+///
+/// Covers the window losing focus or being hidden on desktop, the app being sent to the
+/// background on mobile and the tab being hidden on the web.
+pub fn pause_in_background(
+    mut focus_events: EventReader<WindowFocused>,
+    mut occlusion_events: EventReader<WindowOccluded>,
+    mut lifecycle_events: EventReader<AppLifecycle>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    settings: Res<Settings>,
+) {
+    // Only the latest state counts, reading through all events so none is left over for later.
+    let lost_focus = focus_events
+        .read()
+        .last()
+        .is_some_and(|event| !event.focused);
+    let occluded = occlusion_events
+        .read()
+        .last()
+        .is_some_and(|event| event.occluded);
+    let suspended = lifecycle_events
+        .read()
+        .last()
+        .is_some_and(|event| matches!(event, AppLifecycle::WillSuspend | AppLifecycle::Suspended));
+    if !settings.pause_in_background || *state.get() != GameState::Running {
+        return;
+    }
+    if lost_focus || occluded || suspended || is_page_hidden() {
+        next_state.set(GameState::Paused);
+    }
+}
+
+/// Browsers may stop sending frames to a hidden tab before the window reports anything, so the
+/// page visibility is checked directly.
+#[cfg(target_arch = "wasm32")]
+fn is_page_hidden() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .is_some_and(|document| document.hidden())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_page_hidden() -> bool {
+    false
+}
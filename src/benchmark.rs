@@ -0,0 +1,147 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::{
+    Apple, BodyPart, Direction, GameState, Head, LastDirection, MovementEvent, Obstacle, Score,
+};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use std::env;
+use std::time::Duration;
+
+/// Seed used for benchmarks unless `SNAKE_SEED` is set, so runs are comparable.
+pub const BENCHMARK_SEED: u64 = 0;
+
+/// Runs the game for a fixed number of steps with the snake steered by an autopilot, enabled by
+/// setting `SNAKE_BENCHMARK` to the number of steps.
+///
+/// The snake moves one step per frame instead of waiting for the movement timer, runs restart
+/// right away after a game over and the app exits with a summary on stdout once all steps are
+/// taken. Benchmarks use the default settings with an unlimited frame rate and don't save high
+/// scores or achievements. There is no headless mode, so a window still opens.
+#[derive(Resource)]
+pub struct Benchmark {
+    steps_left: u32,
+    steps: u32,
+    frames: u32,
+    elapsed: Duration,
+    slowest_frame: Duration,
+    most_entities: usize,
+    runs: u32,
+    best_score: u32,
+}
+
+impl Benchmark {
+    pub fn from_env() -> Option<Self> {
+        let steps = match env::var("SNAKE_BENCHMARK").map(|steps| steps.parse::<u32>()) {
+            Ok(Ok(steps)) => steps,
+            Ok(Err(err)) => panic!("SNAKE_BENCHMARK must be a number: {err}"),
+            Err(_) => return None,
+        };
+        Some(Self {
+            steps_left: steps,
+            steps,
+            frames: 0,
+            elapsed: Duration::ZERO,
+            slowest_frame: Duration::ZERO,
+            most_entities: 0,
+            runs: 1,
+            best_score: 0,
+        })
+    }
+
+    /// Single line of space separated `key=value` pairs, prefixed with `benchmark` to find it
+    /// among the log output.
+    fn summary(&self, entities: usize) -> String {
+        let elapsed = self.elapsed.as_secs_f64();
+        let mean_frame = if self.frames == 0 {
+            0.0
+        } else {
+            elapsed / self.frames as f64
+        };
+        format!(
+            "benchmark steps={} frames={} elapsed_s={:.3} mean_frame_ms={:.3} max_frame_ms={:.3} \
+             entities={} max_entities={} runs={} best_score={}",
+            self.steps,
+            self.frames,
+            elapsed,
+            mean_frame * 1000.0,
+            self.slowest_frame.as_secs_f64() * 1000.0,
+            entities,
+            self.most_entities,
+            self.runs,
+            self.best_score,
+        )
+    }
+}
+
+pub fn record_benchmark_frame(
+    mut benchmark: ResMut<Benchmark>,
+    entities: Query<Entity>,
+    time: Res<Time<Real>>,
+) {
+    benchmark.frames += 1;
+    benchmark.elapsed += time.delta();
+    benchmark.slowest_frame = benchmark.slowest_frame.max(time.delta());
+    benchmark.most_entities = benchmark.most_entities.max(entities.iter().count());
+}
+
+/// Takes a step every frame and exits once all steps are taken.
+pub fn step_benchmark(
+    mut benchmark: ResMut<Benchmark>,
+    mut movement_event: EventWriter<MovementEvent>,
+    mut exit: EventWriter<AppExit>,
+    score: Query<&Score>,
+    entities: Query<Entity>,
+) {
+    if benchmark.steps_left == 0 {
+        benchmark.best_score = benchmark.best_score.max(score.single().0);
+        println!("{}", benchmark.summary(entities.iter().count()));
+        exit.send(AppExit::Success);
+        return;
+    }
+    benchmark.steps_left -= 1;
+    movement_event.send(MovementEvent);
+}
+
+pub fn restart_benchmark_run(
+    mut benchmark: ResMut<Benchmark>,
+    mut next_state: ResMut<NextState<GameState>>,
+    score: Query<&Score>,
+) {
+    benchmark.best_score = benchmark.best_score.max(score.single().0);
+    benchmark.runs += 1;
+    next_state.set(GameState::Running);
+}
+
+/// Greedily steers toward the apple, avoiding cells that would end the run right away.
+#[allow(clippy::type_complexity)]
+pub fn steer_autopilot(
+    mut query: Query<(&mut Direction, &LastDirection)>,
+    head: Query<&Cell, With<Head>>,
+    apple: Query<&Cell, With<Apple>>,
+    blocked: Query<&Cell, Or<(With<BodyPart>, With<Obstacle>)>>,
+    board: Res<Board>,
+) {
+    let (mut direction, last_direction) = query.single_mut();
+    let head = head.single();
+    let Ok(apple) = apple.get_single() else {
+        return;
+    };
+    let blocked = blocked.iter().copied().collect::<HashSet<_>>();
+
+    let turn = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ]
+    .into_iter()
+    .filter(|turn| turn.to_ivec2() != -last_direction.0.to_ivec2())
+    .map(|turn| (board.wrap(Cell(head.0 + turn.to_ivec2())), turn))
+    .filter(|(cell, _)| board.contains(*cell) && !blocked.contains(cell))
+    .min_by_key(|(cell, _)| (cell.0 - apple.0).abs().element_sum())
+    .map(|(_, turn)| turn);
+    if let Some(turn) = turn {
+        *direction = turn;
+    }
+}
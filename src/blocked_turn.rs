@@ -0,0 +1,38 @@
+use crate::Head;
+use crate::settings::Settings;
+use bevy::prelude::*;
+
+/// How long the head flashes after a blocked reversal.
+const FLASH_SECONDS: f32 = 0.25;
+const FLASH_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+/// A turn back into the neck was ignored, which the player may not notice otherwise.
+#[derive(Event)]
+pub struct BlockedTurnEvent;
+
+/// Time left of the head's flash after a blocked turn, `None` while it isn't flashing.
+#[derive(Resource, Default)]
+pub struct BlockedTurnFlash(Option<Timer>);
+
+/// Flashes the head red and fades it back to normal, if the assist is turned on.
+pub fn flash_blocked_turn(
+    mut blocked_turn_event: EventReader<BlockedTurnEvent>,
+    mut flash: ResMut<BlockedTurnFlash>,
+    mut head: Query<&mut Sprite, With<Head>>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    if blocked_turn_event.read().count() > 0 && settings.blocked_turn_feedback {
+        flash.0 = Some(Timer::from_seconds(FLASH_SECONDS, TimerMode::Once));
+    }
+    let Some(timer) = flash.0.as_mut() else {
+        return;
+    };
+    timer.tick(time.delta());
+    for mut sprite in head.iter_mut() {
+        sprite.color = FLASH_COLOR.mix(&Color::WHITE, timer.fraction());
+    }
+    if timer.finished() {
+        flash.0 = None;
+    }
+}
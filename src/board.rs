@@ -0,0 +1,282 @@
+use crate::Custom;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use bevy::prelude::*;
+
+/// What happens when the snake leaves the board.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WallMode {
+    /// The snake re-enters on the opposite edge.
+    #[default]
+    Wrap,
+    /// The snake runs into the wall.
+    Solid,
+}
+
+/// Geometry of the square board of the current run, the single source for converting between
+/// cells and world positions.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct Board {
+    /// Number of cells on each side of the center cell.
+    pub half_extent: i32,
+    /// Side length of a cell in world units.
+    pub cell_size: f32,
+    /// World position of the center cell.
+    pub origin: Vec2,
+    pub wall_mode: WallMode,
+}
+
+impl Board {
+    /// Creates a board centered in the world with `extent` cells along each side, rounded up to
+    /// an odd number so there is a center cell.
+    pub fn new(extent: i32, cell_size: f32) -> Self {
+        Self {
+            half_extent: extent / 2,
+            cell_size,
+            origin: Vec2::ZERO,
+            wall_mode: WallMode::default(),
+        }
+    }
+
+    /// Board of the next run as chosen in the settings, risk runs always have solid walls.
+    pub fn from_settings(settings: &Settings, cell_size: f32) -> Self {
+        let mut board = Self::new(settings.board_size, cell_size);
+        if settings.solid_walls || settings.risk_mode {
+            board.wall_mode = WallMode::Solid;
+        }
+        board
+    }
+
+    /// Number of cells along each side of the board.
+    pub fn extent(&self) -> i32 {
+        2 * self.half_extent + 1
+    }
+
+    /// Side length of the whole board in world units.
+    pub fn world_size(&self) -> f32 {
+        self.cell_size * self.extent() as f32
+    }
+
+    /// World position of the center of a cell.
+    pub fn cell_to_world(&self, cell: Cell) -> Vec2 {
+        self.origin + cell.0.as_vec2() * self.cell_size
+    }
+
+    /// Cell a world position lies in, which may be outside of the board.
+    pub fn world_to_cell(&self, position: Vec2) -> Cell {
+        Cell(
+            ((position - self.origin) / self.cell_size)
+                .round()
+                .as_ivec2(),
+        )
+    }
+
+    /// Steps along each axis on the shortest way between two cells, which may be through the
+    /// edges if the walls wrap.
+    pub fn offset(&self, from: Cell, to: Cell) -> IVec2 {
+        let delta = to.0 - from.0;
+        match self.wall_mode {
+            WallMode::Solid => delta,
+            WallMode::Wrap => {
+                let half_extent = IVec2::splat(self.half_extent);
+                (delta + half_extent).rem_euclid(IVec2::splat(self.extent())) - half_extent
+            }
+        }
+    }
+
+    /// Fewest steps between two cells, which may be through the edges if the walls wrap.
+    pub fn distance(&self, from: Cell, to: Cell) -> u32 {
+        let delta = self.offset(from, to).abs();
+        delta.x as u32 + delta.y as u32
+    }
+
+    pub fn contains(&self, cell: Cell) -> bool {
+        cell.x.abs() <= self.half_extent && cell.y.abs() <= self.half_extent
+    }
+
+    /// Brings a cell that left the board back onto it if the walls wrap, solid walls leave it
+    /// off the board.
+    pub fn wrap(&self, cell: Cell) -> Cell {
+        match self.wall_mode {
+            WallMode::Solid => cell,
+            WallMode::Wrap => Cell(
+                (cell.0 + self.half_extent).rem_euclid(IVec2::splat(self.extent()))
+                    - self.half_extent,
+            ),
+        }
+    }
+
+    /// All cells of the board, column by column from the bottom left, always in the same order.
+    pub fn cells(&self) -> impl Iterator<Item = Cell> {
+        let half_extent = self.half_extent;
+        (-half_extent..=half_extent)
+            .flat_map(move |x| (-half_extent..=half_extent).map(move |y| Cell::new(x, y)))
+    }
+}
+
+/// Playable area and walls, rebuilt whenever a run starts so board size changes take effect.
+#[derive(Component)]
+pub struct BoardGeometry;
+
+/// Ground the snake moves on, inside the walls.
+#[derive(Component)]
+pub struct PlayableArea;
+
+/// Wall or tunnel dash around the playable area, drawn in `color` when nothing highlights it.
+#[derive(Component)]
+pub struct Wall {
+    pub color: Color,
+}
+
+/// Spawns the playable area with its walls, which are drawn as dashed tunnels if `tunnels` is
+/// set and the snake wraps through them.
+pub fn spawn_board(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    color_materials: &mut Assets<ColorMaterial>,
+    board: &Board,
+    tunnels: bool,
+) {
+    let size = board.cell_size;
+    let playable_area = Vec2::splat(board.world_size());
+    commands.spawn((
+        Custom,
+        BoardGeometry,
+        PlayableArea,
+        Mesh2d(meshes.add(Rectangle::from_size(playable_area))),
+        MeshMaterial2d(color_materials.add(Color::srgb(0.1, 0.5, 0.3))),
+        Transform::from_translation(board.origin.extend(-2.0)),
+    ));
+
+    if tunnels && board.wall_mode == WallMode::Wrap {
+        spawn_tunnels(commands, meshes, color_materials, board);
+        return;
+    }
+
+    let color = Color::srgb(0.3, 0.7, 0.6);
+    let wall_color = color_materials.add(color);
+    let horizontal_wall = meshes.add(Rectangle::new(size / 2.0, playable_area.y));
+    let vertical_wall = meshes.add(Rectangle::new(playable_area.x, size / 2.0));
+    let wall_offset = (board.half_extent as f32 + 0.5) * size;
+    for (wall, position) in [
+        (horizontal_wall.clone(), Vec2::new(-wall_offset, 0.0)),
+        (horizontal_wall, Vec2::new(wall_offset, 0.0)),
+        (vertical_wall.clone(), Vec2::new(0.0, -wall_offset)),
+        (vertical_wall, Vec2::new(0.0, wall_offset)),
+    ] {
+        commands.spawn((
+            Custom,
+            BoardGeometry,
+            Wall { color },
+            Mesh2d(wall),
+            MeshMaterial2d(wall_color.clone()),
+            Transform::from_translation((board.origin + position).extend(0.0)),
+        ));
+    }
+}
+
+/// One thin dash per cell along every edge, leaving gaps where the snake passes through.
+fn spawn_tunnels(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    color_materials: &mut Assets<ColorMaterial>,
+    board: &Board,
+) {
+    let size = board.cell_size;
+    let color = Color::srgba(0.3, 0.7, 0.6, 0.6);
+    let dash_color = color_materials.add(color);
+    let vertical_dash = meshes.add(Rectangle::new(size / 4.0, size / 2.0));
+    let horizontal_dash = meshes.add(Rectangle::new(size / 2.0, size / 4.0));
+    let wall_offset = (board.half_extent as f32 + 0.5) * size;
+    for i in -board.half_extent..=board.half_extent {
+        let along = i as f32 * size;
+        for (dash, position) in [
+            (vertical_dash.clone(), Vec2::new(-wall_offset, along)),
+            (vertical_dash.clone(), Vec2::new(wall_offset, along)),
+            (horizontal_dash.clone(), Vec2::new(along, -wall_offset)),
+            (horizontal_dash.clone(), Vec2::new(along, wall_offset)),
+        ] {
+            commands.spawn((
+                Custom,
+                BoardGeometry,
+                Wall { color },
+                Mesh2d(dash),
+                MeshMaterial2d(dash_color.clone()),
+                Transform::from_translation((board.origin + position).extend(0.0)),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances_take_the_shortest_way_through_wrapping_walls() {
+        let mut board = Board::new(9, 1.0);
+        let (from, to) = (Cell::new(-4, 3), Cell::new(4, -1));
+        assert_eq!(board.offset(from, to), IVec2::new(-1, -4));
+        assert_eq!(board.offset(to, from), IVec2::new(1, 4));
+        assert_eq!(board.distance(from, to), 5);
+
+        board.wall_mode = WallMode::Solid;
+        assert_eq!(board.offset(from, to), IVec2::new(8, -4));
+        assert_eq!(board.distance(from, to), 12);
+        assert_eq!(board.distance(to, to), 0);
+    }
+
+    #[test]
+    fn cells_round_trip_through_world_positions() {
+        let mut board = Board::new(15, 50.0);
+        board.origin = Vec2::new(-12.5, 30.0);
+        for cell in board.cells().chain([Cell::new(-9, 8), Cell::new(20, -20)]) {
+            assert_eq!(board.world_to_cell(board.cell_to_world(cell)), cell);
+        }
+    }
+
+    #[test]
+    fn positions_round_to_the_closest_cell() {
+        let board = Board::new(15, 50.0);
+        let center = board.cell_to_world(Cell::new(2, -3));
+        for nudge in [
+            Vec2::splat(0.001),
+            Vec2::new(-24.9, 24.9),
+            Vec2::new(24.9, -1.0),
+        ] {
+            assert_eq!(board.world_to_cell(center + nudge), Cell::new(2, -3));
+        }
+    }
+
+    #[test]
+    fn wrapping_many_times_returns_to_the_start() {
+        let board = Board::new(13, 1.0);
+        let start = Cell::new(3, -5);
+        for direction in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+            let mut cell = start;
+            for step in 1..=1000 * board.extent() {
+                cell = board.wrap(Cell(cell.0 + direction));
+                assert!(board.contains(cell));
+                if step % board.extent() == 0 {
+                    assert_eq!(cell, start, "after {step} steps along {direction}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn wraps_negative_coordinates() {
+        let board = Board::new(13, 1.0);
+        assert_eq!(board.wrap(Cell::new(-7, 0)), Cell::new(6, 0));
+        assert_eq!(board.wrap(Cell::new(0, -7)), Cell::new(0, 6));
+        assert_eq!(board.wrap(Cell::new(-20, -33)), Cell::new(6, 6));
+        assert_eq!(board.wrap(Cell::new(7, 7)), Cell::new(-6, -6));
+    }
+
+    #[test]
+    fn solid_walls_leave_cells_off_the_board() {
+        let mut board = Board::new(13, 1.0);
+        board.wall_mode = WallMode::Solid;
+        assert_eq!(board.wrap(Cell::new(-7, 0)), Cell::new(-7, 0));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::board::{Board, WallMode};
+use crate::grid::Cell;
+use crate::options::OptionsMenu;
+use crate::scenario::{load_selected_scenario, placeable_obstacles};
+use crate::settings::Settings;
+use crate::start_cells;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Side length of the previewed board in logical pixels.
+const PREVIEW_SIZE: f32 = 160.0;
+
+/// Small static picture of the board the next run starts on, next to the options that shape
+/// it, like the board size, walls and scenario.
+#[derive(Component)]
+pub struct BoardPreview;
+
+/// Redraws the preview from scratch. It's only a few sprites and only happens when a setting
+/// changes, so nothing is worth reusing.
+pub fn draw_board_preview(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    previews: Query<Entity, With<BoardPreview>>,
+) {
+    for preview in previews.iter() {
+        commands.entity(preview).despawn_recursive();
+    }
+
+    let mut board = Board::from_settings(&settings, 1.0);
+    board.cell_size = PREVIEW_SIZE / board.extent() as f32;
+    let start = start_cells(&settings.start_direction);
+    let scenario = load_selected_scenario(&settings);
+    let cell_sprite = |color: Color, cell: Cell| {
+        (
+            Sprite::from_color(color, Vec2::splat(board.cell_size)),
+            Transform::from_translation(board.cell_to_world(cell).extend(2.0)),
+        )
+    };
+
+    let resolution = &window.single().resolution;
+    let (wall_width, wall_color) = match board.wall_mode {
+        WallMode::Solid => (4.0, Color::srgb(0.3, 0.7, 0.6)),
+        WallMode::Wrap => (1.0, Color::srgba(0.3, 0.7, 0.6, 0.6)),
+    };
+    commands
+        .spawn((
+            // Part of the menu, so it goes away with it.
+            OptionsMenu,
+            BoardPreview,
+            Transform::from_xyz(
+                resolution.width() / 2.0 - 40.0 - PREVIEW_SIZE / 2.0,
+                0.0,
+                6.0,
+            ),
+            Visibility::default(),
+        ))
+        .with_children(|parent| {
+            parent.spawn(Sprite::from_color(
+                wall_color,
+                Vec2::splat(PREVIEW_SIZE + 2.0 * wall_width),
+            ));
+            parent.spawn((
+                Sprite::from_color(Color::srgb(0.1, 0.5, 0.3), Vec2::splat(PREVIEW_SIZE)),
+                Transform::from_xyz(0.0, 0.0, 1.0),
+            ));
+            for cell in placeable_obstacles(&scenario, &board, &start) {
+                parent.spawn(cell_sprite(Color::srgb(0.2, 0.25, 0.25), cell));
+            }
+            if let Some(cell) = scenario.apple_cell().filter(|cell| board.contains(*cell)) {
+                parent.spawn(cell_sprite(Color::srgb(0.9, 0.2, 0.2), cell));
+            }
+            for cell in start.into_iter().filter(|cell| board.contains(*cell)) {
+                parent.spawn(cell_sprite(Color::srgb(0.6, 0.85, 0.3), cell));
+            }
+        });
+}
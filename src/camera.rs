@@ -0,0 +1,114 @@
+use crate::board::Board;
+use crate::settings::{Settings, save_settings};
+use crate::{Custom, Head};
+use bevy::prelude::*;
+use bevy::render::view::RenderLayers;
+
+/// Layer of everything pinned to the screen, like the score, so it stays in place while the
+/// game camera follows the head. It's drawn by a second camera that never moves.
+const HUD_LAYER: usize = 1;
+/// How quickly the camera catches up with the head, higher is snappier.
+const FOLLOW_SPEED: f32 = 8.0;
+/// Zoom change per key press.
+pub const ZOOM_STEP: f32 = 0.25;
+pub const MIN_ZOOM: f32 = 0.5;
+pub const MAX_ZOOM: f32 = 2.0;
+
+/// Camera looking at the board, centered on it unless it follows the head.
+#[derive(Component)]
+pub struct GameCamera;
+
+pub fn hud_layer() -> RenderLayers {
+    RenderLayers::layer(HUD_LAYER)
+}
+
+pub fn spawn_cameras(commands: &mut Commands, settings: &Settings) {
+    commands.spawn((
+        Custom,
+        GameCamera,
+        Camera2d,
+        OrthographicProjection {
+            scale: settings.camera_zoom.recip(),
+            ..OrthographicProjection::default_2d()
+        },
+        settings.anti_aliasing.msaa(),
+    ));
+    commands.spawn((
+        Custom,
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            ..default()
+        },
+        hud_layer(),
+        // UI nodes like the scores are screen-fixed too.
+        IsDefaultUiCamera,
+        settings.anti_aliasing.msaa(),
+    ));
+}
+
+/// Eases the camera toward the head if it follows it and back to the board otherwise, so wraps
+/// and switching the setting don't jump.
+pub fn follow_head(
+    settings: Res<Settings>,
+    board: Res<Board>,
+    head: Query<&Transform, (With<Head>, Without<GameCamera>)>,
+    mut camera: Query<&mut Transform, With<GameCamera>>,
+    time: Res<Time>,
+) {
+    let target = if settings.follow_camera {
+        let Ok(head) = head.get_single() else {
+            return;
+        };
+        head.translation.truncate()
+    } else {
+        board.origin
+    };
+    let Ok(mut camera) = camera.get_single_mut() else {
+        return;
+    };
+    let progress = 1.0 - (-FOLLOW_SPEED * time.delta_secs()).exp();
+    let position = camera.translation.truncate().lerp(target, progress);
+    camera.translation = position.extend(camera.translation.z);
+}
+
+/// Zooms in with `+` and out with `-`.
+pub fn change_zoom(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    let mut step = 0.0;
+    if keys.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        step += ZOOM_STEP;
+    }
+    if keys.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        step -= ZOOM_STEP;
+    }
+    if step != 0.0 {
+        settings.camera_zoom = (settings.camera_zoom + step).clamp(MIN_ZOOM, MAX_ZOOM);
+        save_settings(&settings).expect("could not save settings");
+    }
+}
+
+/// Only the game camera zooms, the HUD camera keeps the score and other texts in place.
+pub fn zoom_camera(
+    settings: Res<Settings>,
+    mut projection: Query<&mut OrthographicProjection, With<GameCamera>>,
+) {
+    let scale = settings.camera_zoom.recip();
+    let Ok(mut projection) = projection.get_single_mut() else {
+        return;
+    };
+    if projection.scale != scale {
+        projection.scale = scale;
+    }
+}
+
+/// Menus and overlays are laid out around the center of the board at the window's size, so the
+/// camera returns there and stops zooming whenever the run is interrupted.
+pub fn center_camera(
+    board: Res<Board>,
+    mut camera: Query<(&mut Transform, &mut OrthographicProjection), With<GameCamera>>,
+) {
+    let (mut camera, mut projection) = camera.single_mut();
+    camera.translation = board.origin.extend(camera.translation.z);
+    projection.scale = 1.0;
+}
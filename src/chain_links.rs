@@ -0,0 +1,46 @@
+use crate::grid::Cell;
+use crate::{BodyPart, Head, NextBodyPart};
+use bevy::prelude::*;
+
+const LINK_COLOR: Color = Color::srgb(1.0, 0.0, 1.0);
+/// Color of links through the edges of the board, which span it.
+const WRAP_LINK_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+/// Color of links to parts that don't exist anymore.
+const BROKEN_LINK_COLOR: Color = Color::srgb(1.0, 0.0, 0.0);
+
+/// Draws the link of every body part to the next one, toggled with the `links` console
+/// command, to spot a broken chain right away.
+#[derive(Resource, Default)]
+pub struct ShowChainLinks(pub bool);
+
+/// Draws an arrow from each part towards the next one and circles the head. A link to a part
+/// that is gone shows as a cross, as such a chain can't be walked to the head.
+pub fn draw_chain_links(
+    show_chain_links: Res<ShowChainLinks>,
+    parts: Query<(&GlobalTransform, &Cell, &NextBodyPart, Has<Head>), With<BodyPart>>,
+    mut gizmos: Gizmos,
+) {
+    if !show_chain_links.0 {
+        return;
+    }
+    for (transform, cell, next, is_head) in parts.iter() {
+        let from = transform.translation().truncate();
+        if is_head {
+            gizmos.circle_2d(from, 10.0, LINK_COLOR);
+        }
+        let Some(next) = next.0 else {
+            continue;
+        };
+        let Ok((next_transform, next_cell, _, _)) = parts.get(next) else {
+            gizmos.cross_2d(from, 10.0, BROKEN_LINK_COLOR);
+            continue;
+        };
+        // Neighbors are a cell apart, anything farther must wrap around the board.
+        let is_wrap = (next_cell.0 - cell.0).abs().element_sum() > 1;
+        gizmos.arrow_2d(
+            from,
+            next_transform.translation().truncate(),
+            if is_wrap { WRAP_LINK_COLOR } else { LINK_COLOR },
+        );
+    }
+}
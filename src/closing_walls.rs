@@ -0,0 +1,111 @@
+use crate::board::{Board, BoardGeometry, spawn_board};
+use crate::frenzy::FrenzyApple;
+use crate::grid::Cell;
+use crate::hazard_apple::HazardApple;
+use crate::mirror::{MirrorMode, mirror_cell};
+use crate::path_hint::PathHintPickup;
+use crate::settings::Settings;
+use crate::shield::ShieldPickup;
+use crate::shrink_apple::ShrinkApple;
+use crate::{Apple, BodyPart, GameOverEvent, GameRng, Obstacle};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Smallest number of cells on each side of the center cell the walls close in to.
+const MIN_HALF_EXTENT: i32 = 1;
+
+/// Counts down to the next time the walls close in. The countdown only starts once closing
+/// walls are turned on.
+#[derive(Resource, Default)]
+pub struct ClosingWalls(Option<Timer>);
+
+/// Moves the walls inward by `closing_walls_rows` every `closing_walls` seconds, down to a 3x3
+/// board.
+///
+/// A snake caught outside the new walls ends the run. The apple is moved back onto the board,
+/// while other pickups and obstacles that end up outside are removed.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn close_in_walls(
+    mut commands: Commands,
+    mut closing_walls: ResMut<ClosingWalls>,
+    mut board: ResMut<Board>,
+    mut game_over_event: EventWriter<GameOverEvent>,
+    mut rng: ResMut<GameRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    mut apples: Query<(&mut Cell, &mut Transform), With<Apple>>,
+    others: Query<
+        (Entity, &Cell),
+        (
+            Or<(
+                With<FrenzyApple>,
+                With<ShrinkApple>,
+                With<HazardApple>,
+                With<PathHintPickup>,
+                With<ShieldPickup>,
+                With<Obstacle>,
+            )>,
+            Without<Apple>,
+        ),
+    >,
+    body_parts: Query<&Cell, (With<BodyPart>, Without<Apple>)>,
+    geometry: Query<Entity, With<BoardGeometry>>,
+    mirror_mode: Res<MirrorMode>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    let Some(interval) = settings.closing_walls else {
+        closing_walls.0 = None;
+        return;
+    };
+    let timer = closing_walls
+        .0
+        .get_or_insert_with(|| Timer::from_seconds(interval, TimerMode::Repeating));
+    if !timer.tick(time.delta()).just_finished() || board.half_extent <= MIN_HALF_EXTENT {
+        return;
+    }
+
+    board.half_extent = (board.half_extent - settings.closing_walls_rows).max(MIN_HALF_EXTENT);
+    let is_caught = body_parts.iter().any(|cell| {
+        !board.contains(*cell) || (mirror_mode.0 && !board.contains(mirror_cell(*cell)))
+    });
+    if is_caught {
+        game_over_event.send(GameOverEvent);
+    }
+
+    let mut occupied = body_parts.iter().copied().collect::<HashSet<_>>();
+    for (entity, cell) in others.iter() {
+        if board.contains(*cell) {
+            occupied.insert(*cell);
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+    occupied.extend(apples.iter().map(|(cell, _)| *cell));
+    occupied.retain(|cell| board.contains(*cell));
+    for (mut cell, mut transform) in apples.iter_mut() {
+        if board.contains(*cell) {
+            continue;
+        }
+        if let Some(new_cell) = settings
+            .apple_placement
+            .strategy()
+            .choose(&board, &occupied, &mut rng.0)
+        {
+            *cell = new_cell;
+            occupied.insert(new_cell);
+            transform.translation = board.cell_to_world(new_cell).extend(-1.0);
+        }
+    }
+
+    for entity in geometry.iter() {
+        commands.entity(entity).despawn();
+    }
+    spawn_board(
+        &mut commands,
+        &mut meshes,
+        &mut color_materials,
+        &board,
+        settings.tunnel_walls,
+    );
+}
@@ -0,0 +1,78 @@
+use crate::settings::Settings;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Which collisions end the run, the single place deciding how deadly the current mode is.
+///
+/// Set up from the settings at the start of every run. By default every collision is lethal,
+/// modes only switch off what they forgive:
+///
+/// - zen mode is peaceful, the snake slides over itself and obstacles and bounces off solid walls
+///   as often as it likes.
+/// - wrapping walls can't be hit at all, so `walls` only matters with solid walls, and even then
+///   only once the wall grace is used up.
+/// - `other_snakes` only matters in mirror mode, the only mode with a second snake.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionRules {
+    /// The head running into the snake's own body.
+    pub self_collision: bool,
+    /// The head running into a solid wall with no wall grace left.
+    pub walls: bool,
+    pub obstacles: bool,
+    /// The head running into another snake, like the mirrored one.
+    pub other_snakes: bool,
+}
+
+impl Default for CollisionRules {
+    fn default() -> Self {
+        Self {
+            self_collision: true,
+            walls: true,
+            obstacles: true,
+            other_snakes: true,
+        }
+    }
+}
+
+impl CollisionRules {
+    /// No collision ends the run.
+    pub const PEACEFUL: Self = Self {
+        self_collision: false,
+        walls: false,
+        obstacles: false,
+        other_snakes: false,
+    };
+
+    pub fn from_settings(settings: &Settings) -> Self {
+        if settings.zen_mode {
+            Self::PEACEFUL
+        } else {
+            Self::default()
+        }
+    }
+}
+
+/// Time at the start of a run during which no collision ends it, so a turn still queued from
+/// the last run or an awkward start position can't end the new run right away.
+#[derive(Resource)]
+pub struct SpawnInvulnerability(Timer);
+
+impl SpawnInvulnerability {
+    pub fn new(settings: &Settings) -> Self {
+        Self(Timer::new(
+            Duration::from_secs_f32(settings.spawn_invulnerability),
+            TimerMode::Once,
+        ))
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.0.remaining().is_zero()
+    }
+}
+
+pub fn tick_spawn_invulnerability(
+    mut spawn_invulnerability: ResMut<SpawnInvulnerability>,
+    time: Res<Time>,
+) {
+    spawn_invulnerability.0.tick(time.delta());
+}
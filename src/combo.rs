@@ -0,0 +1,97 @@
+use crate::settings::Settings;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Rewards eating apples in quick succession with a growing score multiplier.
+#[derive(Resource)]
+pub struct Combo {
+    last_apple: Option<Duration>,
+    pub multiplier: u32,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Self {
+            last_apple: None,
+            multiplier: 1,
+        }
+    }
+}
+
+impl Combo {
+    /// Registers an eaten apple and returns the multiplier to score it with.
+    pub fn register_apple(&mut self, now: Duration, settings: &Settings) -> u32 {
+        self.multiplier = if self.is_running(now, settings) {
+            (self.multiplier + 1).min(settings.max_combo)
+        } else {
+            1
+        };
+        self.last_apple = Some(now);
+        self.multiplier
+    }
+
+    fn is_running(&self, now: Duration, settings: &Settings) -> bool {
+        self.last_apple
+            .is_some_and(|last| now - last <= Duration::from_secs_f32(settings.combo_window))
+    }
+}
+
+#[derive(Component)]
+pub struct ComboText;
+
+pub fn expire_combo(mut combo: ResMut<Combo>, settings: Res<Settings>, time: Res<Time>) {
+    if combo.multiplier > 1 && !combo.is_running(time.elapsed(), &settings) {
+        combo.multiplier = 1;
+    }
+}
+
+pub fn update_combo_text(
+    combo: Res<Combo>,
+    mut query: Query<(&mut Text, &mut Visibility), With<ComboText>>,
+) {
+    let (mut text, mut visibility) = query.single_mut();
+    text.0 = format!("Combo: x{}", combo.multiplier);
+    *visibility = if combo.multiplier > 1 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> Settings {
+        Settings {
+            combo_window: 2.0,
+            max_combo: 3,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn quick_apples_raise_the_multiplier_up_to_the_maximum() {
+        let mut combo = Combo::default();
+        let multipliers = [0, 1, 2, 3, 4]
+            .map(|seconds| combo.register_apple(Duration::from_secs(seconds), &settings()));
+        assert_eq!(multipliers, [1, 2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn slow_apples_reset_the_multiplier() {
+        let mut combo = Combo::default();
+        combo.register_apple(Duration::from_secs(0), &settings());
+        assert_eq!(combo.register_apple(Duration::from_secs(2), &settings()), 2);
+        assert_eq!(combo.register_apple(Duration::from_secs(5), &settings()), 1);
+    }
+
+    #[test]
+    fn a_maximum_of_one_disables_combos() {
+        let mut combo = Combo::default();
+        for millis in [0, 100, 200] {
+            let multiplier = combo.register_apple(Duration::from_millis(millis), &default());
+            assert_eq!(multiplier, 1);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::board::Board;
+use crate::chain_links::ShowChainLinks;
+use crate::cursor_coordinates::CursorCoordinates;
+use crate::fonts::GameFont;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::state_dump::dump_state;
+use crate::{
+    Apple, AppleValue, Constants, GameRng, GameState, Growth, MovementTimer,
+    choose_apple_spawn_cell, spawn_apple_at,
+};
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::utils::HashSet;
+use bevy::window::PrimaryWindow;
+use std::env;
+use std::time::Duration;
+
+/// A console command, `run` gets the arguments after the name and returns what to print.
+struct ConsoleCommand {
+    name: &'static str,
+    usage: &'static str,
+    run: fn(&mut World, &[&str]) -> Result<String, String>,
+}
+
+/// All console commands, add an entry here to extend the console.
+const COMMANDS: &[ConsoleCommand] = &[
+    ConsoleCommand {
+        name: "help",
+        usage: "help",
+        run: |_, _| {
+            Ok(COMMANDS
+                .iter()
+                .map(|command| command.usage)
+                .collect::<Vec<_>>()
+                .join("    "))
+        },
+    },
+    ConsoleCommand {
+        name: "grow",
+        usage: "grow <segments>",
+        run: |world, args| {
+            let segments = parse_arg::<u32>(args)?;
+            world.resource_mut::<Growth>().segments += segments;
+            Ok(format!("Growing by {segments} segments"))
+        },
+    },
+    ConsoleCommand {
+        name: "speed",
+        usage: "speed <milliseconds per cell>",
+        run: |world, args| {
+            let millis = parse_arg::<u64>(args)?;
+            if millis == 0 {
+                return Err(String::from("Speed must be at least 1ms"));
+            }
+            for mut timer in world.query::<&mut MovementTimer>().iter_mut(world) {
+                timer.0.set_duration(Duration::from_millis(millis));
+            }
+            Ok(format!("Moving every {millis}ms"))
+        },
+    },
+    ConsoleCommand {
+        name: "spawn",
+        usage: "spawn apple",
+        run: |world, args| match args {
+            ["apple"] => respawn_apple(world),
+            _ => Err(String::from("Usage: spawn apple")),
+        },
+    },
+    ConsoleCommand {
+        name: "coords",
+        usage: "coords <on|off>",
+        run: |world, args| {
+            let show = parse_on_off(args, "coords")?;
+            world.resource_mut::<CursorCoordinates>().0 = show;
+            Ok(format!(
+                "{} the cell under the cursor",
+                if show { "Showing" } else { "Hiding" }
+            ))
+        },
+    },
+    ConsoleCommand {
+        name: "links",
+        usage: "links <on|off>",
+        run: |world, args| {
+            let show = parse_on_off(args, "links")?;
+            world.resource_mut::<ShowChainLinks>().0 = show;
+            Ok(format!(
+                "{} the links between body parts",
+                if show { "Showing" } else { "Hiding" }
+            ))
+        },
+    },
+    ConsoleCommand {
+        name: "dump",
+        usage: "dump",
+        run: |world, _| dump_state(world),
+    },
+    ConsoleCommand {
+        name: "state",
+        usage: "state <running|paused>",
+        run: |world, args| {
+            let state = match args {
+                ["running"] => GameState::Running,
+                ["paused"] => GameState::Paused,
+                _ => return Err(String::from("Usage: state <running|paused>")),
+            };
+            world.resource_mut::<Console>().return_state = state.clone();
+            Ok(format!("Returning to {state:?}"))
+        },
+    },
+];
+
+/// Developer console, available in debug builds or if `SNAKE_CONSOLE` is set.
+#[derive(Resource, Default)]
+pub struct Console {
+    input: String,
+    output: String,
+    submitted: Option<String>,
+    return_state: GameState,
+}
+
+#[derive(Component)]
+pub struct ConsolePanel;
+
+#[derive(Component)]
+pub struct ConsoleText;
+
+pub fn is_console_enabled() -> bool {
+    cfg!(debug_assertions) || env::var_os("SNAKE_CONSOLE").is_some()
+}
+
+pub fn open_console(
+    keys: Res<ButtonInput<KeyCode>>,
+    state: Res<State<GameState>>,
+    mut console: ResMut<Console>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::Backquote) {
+        console.return_state = state.get().clone();
+        next_state.set(GameState::Console);
+    }
+}
+
+pub fn spawn_console(
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    mut keyboard_input: ResMut<Events<KeyboardInput>>,
+) {
+    // Don't let the key that opened the console close it again right away.
+    keyboard_input.clear();
+
+    let resolution = &window.single().resolution;
+    let size = Vec2::new(resolution.width(), 90.0);
+    commands
+        .spawn((
+            ConsolePanel,
+            Mesh2d(meshes.add(Rectangle::from_size(size))),
+            MeshMaterial2d(color_materials.add(Color::srgba(0.0, 0.0, 0.0, 0.85))),
+            Transform::from_xyz(0.0, (resolution.height() - size.y) / 2.0, 6.0),
+        ))
+        .with_child((
+            ConsoleText,
+            Text2d::default(),
+            TextFont {
+                font: game_font.handle.clone(),
+                font_size: 25.0,
+                ..default()
+            },
+            Anchor::CenterLeft,
+            Transform::from_xyz(size.x / -2.0 + 20.0, 0.0, 1.0),
+        ));
+}
+
+pub fn type_in_console(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut console: ResMut<Console>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for input in keyboard_input.read() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+        match (&input.key_code, &input.logical_key) {
+            (KeyCode::Backquote | KeyCode::Escape, _) => {
+                next_state.set(console.return_state.clone());
+                return;
+            }
+            (_, Key::Enter) => {
+                let line = std::mem::take(&mut console.input);
+                console.submitted = Some(line);
+            }
+            (_, Key::Backspace) => {
+                console.input.pop();
+            }
+            (_, Key::Space) => console.input.push(' '),
+            (_, Key::Character(text)) => console.input.push_str(text),
+            _ => {}
+        }
+    }
+}
+
+pub fn run_console_command(world: &mut World) {
+    let Some(line) = world.resource_mut::<Console>().submitted.take() else {
+        return;
+    };
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let Some((name, args)) = words.split_first() else {
+        return;
+    };
+    let output = match COMMANDS.iter().find(|command| command.name == *name) {
+        Some(command) => (command.run)(world, args).unwrap_or_else(|err| err),
+        None => format!("Unknown command {name}, try help"),
+    };
+    world.resource_mut::<Console>().output = output;
+}
+
+pub fn update_console_text(
+    console: Res<Console>,
+    mut query: Query<&mut Text2d, With<ConsoleText>>,
+) {
+    for mut text in query.iter_mut() {
+        text.0 = format!("{}\n> {}_", console.output, console.input);
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &[&str]) -> Result<T, String> {
+    match args {
+        [arg] => arg.parse().map_err(|_| format!("Invalid argument {arg}")),
+        _ => Err(String::from("Expected exactly one argument")),
+    }
+}
+
+fn parse_on_off(args: &[&str], name: &str) -> Result<bool, String> {
+    match args {
+        ["on"] => Ok(true),
+        ["off"] => Ok(false),
+        _ => Err(format!("Usage: {name} <on|off>")),
+    }
+}
+
+/// Replaces the apple with a new one on a random free cell.
+fn respawn_apple(world: &mut World) -> Result<String, String> {
+    let apples = world
+        .query_filtered::<Entity, With<Apple>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    let occupied = world
+        .query::<&Cell>()
+        .iter(world)
+        .copied()
+        .collect::<HashSet<_>>();
+    let board = *world.resource::<Board>();
+    let texture = world.resource::<Constants>().apple_texture_handle.clone();
+    let cell = choose_apple_spawn_cell(&board, &occupied, &mut world.resource_mut::<GameRng>().0)
+        .ok_or_else(|| String::from("No free cell left"))?;
+
+    for apple in apples {
+        world.despawn(apple);
+    }
+    let value = AppleValue(world.resource::<Settings>().apple_value);
+    spawn_apple_at(&mut world.commands(), cell, &board, texture, value);
+    world.flush();
+    Ok(format!("Spawned an apple at {}, {}", cell.x, cell.y))
+}
@@ -0,0 +1,71 @@
+use crate::Custom;
+use crate::camera::hud_layer;
+use crate::settings::Settings;
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::sprite::{AlphaMode2d, Material2d};
+use bevy::window::WindowResolution;
+
+/// Height in logical pixels of one scanline and the gap below it.
+const SCANLINE_HEIGHT: f32 = 4.0;
+
+/// Retro CRT look drawn over the whole window: scanlines, a vignette and a black frame with
+/// curved corners like the bulging glass of an old tube.
+///
+/// The scene itself isn't distorted, the overlay only darkens it.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct CrtMaterial {
+    #[uniform(0)]
+    scanlines: f32,
+}
+
+impl Material2d for CrtMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/crt.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+#[derive(Component)]
+pub struct CrtOverlay;
+
+pub fn spawn_crt_overlay(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    crt_materials: &mut Assets<CrtMaterial>,
+    resolution: &WindowResolution,
+    settings: &Settings,
+) {
+    commands.spawn((
+        Custom,
+        CrtOverlay,
+        hud_layer(),
+        Mesh2d(meshes.add(Rectangle::from_size(resolution.size()))),
+        MeshMaterial2d(crt_materials.add(CrtMaterial {
+            scanlines: resolution.height() / SCANLINE_HEIGHT,
+        })),
+        // Above everything else, including the menus.
+        Transform::from_xyz(0.0, 0.0, 10.0),
+        crt_visibility(settings),
+    ));
+}
+
+pub fn update_crt_overlay(
+    settings: Res<Settings>,
+    mut query: Query<&mut Visibility, With<CrtOverlay>>,
+) {
+    for mut visibility in query.iter_mut() {
+        *visibility = crt_visibility(&settings);
+    }
+}
+
+fn crt_visibility(settings: &Settings) -> Visibility {
+    if settings.crt_effect {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
@@ -0,0 +1,74 @@
+use crate::board::Board;
+use crate::camera::GameCamera;
+use crate::fonts::GameFont;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Distance of the coordinates from the cursor, so the pointer doesn't cover them.
+const CURSOR_OFFSET: Vec2 = Vec2::new(16.0, 16.0);
+
+/// Shows the cell under the mouse cursor, toggled with the `coords` console command. Helps
+/// to name the exact cell when reporting bugs.
+#[derive(Resource, Default)]
+pub struct CursorCoordinates(pub bool);
+
+#[derive(Component)]
+pub struct CursorCoordinatesText;
+
+pub fn spawn_cursor_coordinates_text(mut commands: Commands, game_font: Res<GameFont>) {
+    commands.spawn((
+        CursorCoordinatesText,
+        Text::default(),
+        TextColor(Color::srgb(1.0, 1.0, 0.5)),
+        TextFont {
+            font: game_font.handle.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        Visibility::Hidden,
+    ));
+}
+
+pub fn update_cursor_coordinates(
+    cursor_coordinates: Res<CursorCoordinates>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut text: Query<(&mut Text, &mut Node, &mut Visibility), With<CursorCoordinatesText>>,
+    board: Res<Board>,
+) {
+    let Ok((mut text, mut node, mut visibility)) = text.get_single_mut() else {
+        return;
+    };
+    let (Ok(window), Ok((camera, camera_transform))) = (window.get_single(), camera.get_single())
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    let Some((cursor, position)) = window
+        .cursor_position()
+        .filter(|_| cursor_coordinates.0)
+        .and_then(|cursor| {
+            camera
+                .viewport_to_world_2d(camera_transform, cursor)
+                .ok()
+                .map(|position| (cursor, position))
+        })
+    else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    let cell = board.world_to_cell(position);
+    text.0 = if board.contains(cell) {
+        format!("{}, {}", cell.x, cell.y)
+    } else {
+        format!("{}, {} (outside)", cell.x, cell.y)
+    };
+    node.left = Val::Px(cursor.x + CURSOR_OFFSET.x);
+    node.top = Val::Px(cursor.y + CURSOR_OFFSET.y);
+    *visibility = Visibility::Inherited;
+}
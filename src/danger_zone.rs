@@ -0,0 +1,69 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::{BodyPart, Custom, Direction, Head, Obstacle};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+#[derive(Component)]
+pub struct DangerMark;
+
+pub fn direction_changed(query: Query<(), Changed<Direction>>) -> bool {
+    !query.is_empty()
+}
+
+/// Cells within `lookahead` steps in front of the head that would end the run.
+pub fn danger_cells(
+    head: Cell,
+    direction: &Direction,
+    lookahead: u32,
+    board: &Board,
+    blocked: &HashSet<Cell>,
+) -> Vec<Cell> {
+    let mut cell = head;
+    let mut cells = vec![];
+    for _ in 0..lookahead {
+        cell = board.wrap(Cell(cell.0 + direction.to_ivec2()));
+        if blocked.contains(&cell) {
+            cells.push(cell);
+        }
+    }
+    cells
+}
+
+/// Highlights the cells ahead of the head that are blocked by the snake's own body or an
+/// obstacle, to warn the player before a collision.
+#[allow(clippy::type_complexity)]
+pub fn update_danger_zone(
+    mut commands: Commands,
+    marks: Query<Entity, With<DangerMark>>,
+    head: Query<&Cell, With<Head>>,
+    direction: Query<&Direction>,
+    blocked: Query<&Cell, (Or<(With<BodyPart>, With<Obstacle>)>, Without<Head>)>,
+    settings: Res<Settings>,
+    board: Res<Board>,
+) {
+    for mark in marks.iter() {
+        commands.entity(mark).despawn();
+    }
+    let Some(lookahead) = settings.danger_zone else {
+        return;
+    };
+
+    let blocked = blocked.iter().copied().collect();
+    let size = board.cell_size;
+    for cell in danger_cells(
+        *head.single(),
+        direction.single(),
+        lookahead,
+        &board,
+        &blocked,
+    ) {
+        commands.spawn((
+            Custom,
+            DangerMark,
+            Sprite::from_color(Color::srgba(1.0, 0.1, 0.1, 0.45), Vec2::splat(size)),
+            Transform::from_translation(board.cell_to_world(cell).extend(-0.5)),
+        ));
+    }
+}
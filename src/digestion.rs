@@ -0,0 +1,51 @@
+use crate::settings::Settings;
+use crate::{Head, NextBodyPart, Tail};
+use bevy::prelude::*;
+
+const DIGEST_COLOR: Color = Color::srgb(1.0, 0.35, 0.3);
+/// How much the part that just ate is tinted, fading out towards the tail.
+const DIGEST_STRENGTH: f32 = 0.7;
+
+/// Body part that was the head when it ate an apple.
+///
+/// Parts stay on their cell while the snake moves on, so the marked part ends up one part
+/// further from the head each step, as if the apple traveled down the body.
+#[derive(Component)]
+pub struct Digesting;
+
+pub fn start_digesting(
+    mut commands: Commands,
+    head: Query<Entity, With<Head>>,
+    settings: Res<Settings>,
+) {
+    if settings.show_digestion {
+        commands.entity(head.single()).insert(Digesting);
+    }
+}
+
+/// Tints the digesting parts on top of the body gradient, the less the closer to the tail.
+pub fn tint_digesting_parts(
+    settings: Res<Settings>,
+    tail: Query<Entity, With<Tail>>,
+    mut parts: Query<(&mut Sprite, &NextBodyPart, Has<Digesting>)>,
+) {
+    if !settings.show_digestion {
+        return;
+    }
+    let mut chain = vec![];
+    let mut next = Some(tail.single());
+    while let Some(entity) = next {
+        chain.push(entity);
+        next = parts.get(entity).expect("expected linked body part").1.0;
+    }
+
+    let last = (chain.len() - 1).max(1) as f32;
+    for (index, entity) in chain.into_iter().enumerate() {
+        let (mut sprite, _, is_digesting) =
+            parts.get_mut(entity).expect("expected linked body part");
+        if is_digesting {
+            let strength = DIGEST_STRENGTH * index as f32 / last;
+            sprite.color = sprite.color.mix(&DIGEST_COLOR, strength);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use crate::MovementTimer;
+use crate::risk_mode::RiskMode;
+use crate::settings::Settings;
+use crate::start_grace::StartGrace;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// How much slower than the base interval the snake moves at the very start of a run.
+const START_SLOWDOWN: f32 = 2.5;
+
+/// Counts down the opening ramp of a run; `None` if the ramp is disabled or already over.
+#[derive(Resource)]
+pub struct StartRamp(Option<Timer>);
+
+impl StartRamp {
+    pub fn new(settings: &Settings) -> Self {
+        Self((settings.start_ramp > 0.0).then(|| {
+            Timer::new(
+                Duration::from_secs_f32(settings.start_ramp),
+                TimerMode::Once,
+            )
+        }))
+    }
+}
+
+/// Interpolates from a slow start towards `base` over `ramp`, reaching it exactly at the end.
+pub fn ramped_interval(base: Duration, ramp: Duration, elapsed: Duration) -> Duration {
+    if elapsed >= ramp {
+        return base;
+    }
+    let progress = elapsed.as_secs_f32() / ramp.as_secs_f32();
+    base.mul_f32(START_SLOWDOWN + (1.0 - START_SLOWDOWN) * progress)
+}
+
+/// Starts ramping up only once the grace period is over.
+pub fn ease_in_movement(
+    mut start_ramp: ResMut<StartRamp>,
+    start_grace: Res<StartGrace>,
+    mut movement_timer: Query<&mut MovementTimer>,
+    risk_mode: Res<RiskMode>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    if start_grace.is_active() {
+        return;
+    }
+    let Some(ramp) = start_ramp.0.as_mut() else {
+        return;
+    };
+    ramp.tick(time.delta());
+
+    let base = risk_mode.base_interval(&settings);
+    movement_timer.single_mut().0.set_duration(ramped_interval(
+        base,
+        ramp.duration(),
+        ramp.elapsed(),
+    ));
+    // Hand over to the base speed, so later speed changes aren't overwritten.
+    if ramp.finished() {
+        start_ramp.0 = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk_mode::RiskMode;
+    use bevy::time::TimeUpdateStrategy;
+
+    const BASE: Duration = Duration::from_millis(100);
+    const RAMP: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn ramp_starts_slow_and_ends_at_the_base() {
+        assert_eq!(
+            ramped_interval(BASE, RAMP, Duration::ZERO),
+            BASE.mul_f32(START_SLOWDOWN)
+        );
+        assert!(ramped_interval(BASE, RAMP, RAMP / 2) > BASE);
+        assert_eq!(ramped_interval(BASE, RAMP, RAMP), BASE);
+        assert_eq!(ramped_interval(BASE, RAMP, RAMP * 3), BASE);
+    }
+
+    #[test]
+    fn ramp_speeds_up_steadily() {
+        let intervals = (0..=20)
+            .map(|tenth| ramped_interval(BASE, RAMP, RAMP * tenth / 20))
+            .collect::<Vec<_>>();
+        assert!(intervals.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn interval_reaches_the_base_after_the_ramp() {
+        let settings = Settings {
+            start_ramp: RAMP.as_secs_f32(),
+            ..default()
+        };
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .insert_resource(StartRamp::new(&settings))
+            .insert_resource(StartGrace::new(&settings))
+            .insert_resource(RiskMode(false))
+            .insert_resource(settings)
+            .add_systems(Update, ease_in_movement);
+        let timer = app
+            .world_mut()
+            .spawn(MovementTimer(Timer::new(BASE, TimerMode::Repeating)))
+            .id();
+        let interval = |app: &App| {
+            app.world()
+                .get::<MovementTimer>(timer)
+                .unwrap()
+                .0
+                .duration()
+        };
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(interval(&app) > BASE);
+
+        for _ in 0..20 {
+            app.update();
+        }
+        assert_eq!(interval(&app), BASE);
+        assert!(app.world().resource::<StartRamp>().0.is_none());
+    }
+}
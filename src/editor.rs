@@ -0,0 +1,173 @@
+use crate::board::Board;
+use crate::camera::GameCamera;
+use crate::fonts::GameFont;
+use crate::scenario::{EDITOR_SCENARIO, Scenario, load_scenario, save_scenario};
+use crate::settings::Settings;
+use crate::{Constants, GameState, start_cells};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+const HELP: &str = "Left click: Obstacle    Right click: Apple    S: Save    Esc: Back";
+
+/// Layout being edited, saved to the editor scenario on demand.
+#[derive(Resource, Default)]
+pub struct EditorLayout(Scenario);
+
+/// Everything shown while editing, covering the running game.
+#[derive(Component)]
+pub struct EditorUi;
+
+#[derive(Component)]
+pub struct EditorMark;
+
+#[derive(Component)]
+pub struct EditorStatus;
+
+pub fn open_editor(keys: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if keys.just_pressed(KeyCode::KeyE) {
+        next_state.set(GameState::Editor);
+    }
+}
+
+pub fn spawn_editor(
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+) {
+    let scenario = load_scenario(EDITOR_SCENARIO).unwrap_or_else(|err| {
+        warn!("could not load scenario, starting with an empty one: {err}");
+        Scenario::default()
+    });
+
+    let resolution = &window.single().resolution;
+    let size = board.cell_size;
+    commands.spawn((
+        EditorUi,
+        Mesh2d(meshes.add(Rectangle::from_size(resolution.size()))),
+        MeshMaterial2d(color_materials.add(Color::srgb(0.1, 0.1, 0.1))),
+        Transform::from_xyz(0.0, 0.0, 5.0),
+    ));
+    commands.spawn((
+        EditorUi,
+        Mesh2d(meshes.add(Rectangle::from_size(Vec2::splat(board.world_size())))),
+        MeshMaterial2d(color_materials.add(Color::srgb(0.1, 0.5, 0.3))),
+        Transform::from_translation(board.origin.extend(5.1)),
+    ));
+    // A snake placed by the scenario replaces the usual start.
+    let snake = if scenario.snake.is_empty() {
+        start_cells(&settings.start_direction).to_vec()
+    } else {
+        scenario.snake_cells().collect()
+    };
+    for cell in snake {
+        commands.spawn((
+            EditorUi,
+            Sprite::from_color(Color::srgba(0.25, 0.45, 0.12, 0.6), Vec2::splat(size)),
+            Transform::from_translation(board.cell_to_world(cell).extend(5.2)),
+        ));
+    }
+    commands.insert_resource(EditorLayout(scenario));
+    commands.spawn((
+        EditorUi,
+        EditorStatus,
+        Text2d::new(HELP),
+        TextFont {
+            font: game_font.handle.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, resolution.height() / -2.0 + 20.0, 5.5),
+    ));
+}
+
+#[allow(clippy::type_complexity)]
+pub fn despawn_editor(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<EditorUi>, With<EditorMark>)>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<EditorLayout>();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn edit_layout(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut layout: ResMut<EditorLayout>,
+    mut status: Query<&mut Text2d, With<EditorStatus>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Paused);
+        return;
+    }
+
+    let mut status = status.single_mut();
+    if keys.just_pressed(KeyCode::KeyS) {
+        status.0 = match save_scenario(EDITOR_SCENARIO, &layout.0) {
+            Ok(()) => format!("Saved as scenario \"{EDITOR_SCENARIO}\""),
+            Err(err) => format!("Could not save scenario: {err}"),
+        };
+        return;
+    }
+
+    let (camera, camera_transform) = camera.single();
+    let Some(cell) = window
+        .single()
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+        .map(|position| board.world_to_cell(position))
+        .filter(|cell| {
+            board.contains(*cell) && !start_cells(&settings.start_direction).contains(cell)
+        })
+    else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) {
+        layout.0.toggle_obstacle(cell);
+    } else if mouse.just_pressed(MouseButton::Right) {
+        layout.0.toggle_apple(cell);
+    } else {
+        return;
+    }
+    status.0 = String::from(HELP);
+}
+
+pub fn update_editor_marks(
+    mut commands: Commands,
+    layout: Res<EditorLayout>,
+    marks: Query<Entity, With<EditorMark>>,
+    board: Res<Board>,
+    constants: Res<Constants>,
+) {
+    for mark in marks.iter() {
+        commands.entity(mark).despawn();
+    }
+
+    let size = board.cell_size;
+    for cell in layout.0.obstacle_cells() {
+        commands.spawn((
+            EditorMark,
+            Sprite::from_color(Color::srgb(0.2, 0.25, 0.25), Vec2::splat(size)),
+            Transform::from_translation(board.cell_to_world(cell).extend(5.3)),
+        ));
+    }
+    if let Some(cell) = layout.0.apple_cell() {
+        commands.spawn((
+            EditorMark,
+            Sprite::from_image(constants.apple_texture_handle.clone()),
+            Transform::from_translation(board.cell_to_world(cell).extend(5.3)),
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+use std::path::Path;
+
+/// Names of the files in `directory` with one of the `extensions`, or all of them if no
+/// extensions are given, sorted by name. A missing directory has no files.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn list_files(directory: impl AsRef<Path>, extensions: &[&str]) -> Vec<String> {
+    let mut names = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| {
+                    extensions.is_empty()
+                        || extensions
+                            .iter()
+                            .any(|extension| name.ends_with(&format!(".{extension}")))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// The web has no directories to look into, so there is nothing to choose from.
+#[cfg(target_arch = "wasm32")]
+pub fn list_files(_directory: impl AsRef<Path>, _extensions: &[&str]) -> Vec<String> {
+    Vec::new()
+}
@@ -0,0 +1,93 @@
+use crate::board::Board;
+use crate::frenzy::FrenzyApple;
+use crate::grid::Cell;
+use crate::mirror::MirrorMode;
+use crate::settings::Settings;
+use crate::shrink_apple::ShrinkApple;
+use crate::{
+    Apple, BodyPart, Constants, Direction, GameRng, Growth, LastDirection, Obstacle, spawn_apple,
+    spawn_snake,
+};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Cells of a snake filling all but the top two rows of the board, from tail to head.
+///
+/// The body winds through the rows from the bottom up, alternating between moving right and
+/// left, and the head sits one row above its last cell facing up, so the next step is into the
+/// free top row.
+fn serpentine_cells(board: &Board) -> Vec<Cell> {
+    let half_extent = board.half_extent;
+    let mut cells = vec![];
+    for (row, y) in (-half_extent..half_extent - 1).enumerate() {
+        let columns = -half_extent..=half_extent;
+        if row % 2 == 0 {
+            cells.extend(columns.map(|x| Cell::new(x, y)));
+        } else {
+            cells.extend(columns.rev().map(|x| Cell::new(x, y)));
+        }
+    }
+    let last = *cells.last().expect("expected at least one filled row");
+    cells.push(Cell(last.0 + IVec2::Y));
+    cells
+}
+
+/// Replaces the snake with one filling most of the board when pressing F, to try out how the
+/// game behaves when nearly every cell is taken without playing that long.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn fill_board(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut rng: ResMut<GameRng>,
+    mut growth: ResMut<Growth>,
+    board: Res<Board>,
+    constants: Res<Constants>,
+    mirror_mode: Res<MirrorMode>,
+    current: Query<
+        Entity,
+        Or<(
+            With<BodyPart>,
+            With<Apple>,
+            With<FrenzyApple>,
+            With<ShrinkApple>,
+        )>,
+    >,
+    obstacles: Query<&Cell, With<Obstacle>>,
+    mut direction: Query<(&mut Direction, &mut LastDirection)>,
+    settings: Res<Settings>,
+) {
+    if !keys.just_pressed(KeyCode::KeyF) {
+        return;
+    }
+    if mirror_mode.0 {
+        warn!("can't fill the board in mirror mode, the snake would overlap its mirror image");
+        return;
+    }
+    let cells = serpentine_cells(&board);
+    let mut occupied = obstacles.iter().copied().collect::<HashSet<_>>();
+    if cells.iter().any(|cell| occupied.contains(cell)) {
+        warn!("can't fill the board, obstacles are in the way");
+        return;
+    }
+
+    for entity in current.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    spawn_snake(&mut commands, &cells, &board, &constants);
+
+    occupied.extend(cells.iter().copied());
+    spawn_apple(
+        &mut commands,
+        &mut rng,
+        &board,
+        constants.apple_texture_handle.clone(),
+        &occupied,
+        &settings,
+    );
+
+    let (mut direction, mut last_direction) = direction.single_mut();
+    *direction = Direction::Up;
+    last_direction.0 = Direction::Up;
+    *growth = Growth::default();
+}
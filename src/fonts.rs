@@ -0,0 +1,92 @@
+use crate::files::list_files;
+use crate::settings::Settings;
+use bevy::asset::AssetLoadFailedEvent;
+use bevy::prelude::*;
+use std::path::Path;
+
+const DEFAULT_FONT: &str = "fonts/upheavtt.ttf";
+/// Directory relative to `assets` in which users can put fonts to choose from in the options.
+pub const CUSTOM_FONT_DIRECTORY: &str = "fonts/custom";
+
+/// Font of every text in the game, the one chosen in the settings or the built-in one.
+#[derive(Resource)]
+pub struct GameFont {
+    /// Custom font the handle was loaded for, to notice when the setting changes.
+    name: Option<String>,
+    pub handle: Handle<Font>,
+}
+
+impl FromWorld for GameFont {
+    fn from_world(world: &mut World) -> Self {
+        let name = world.resource::<Settings>().font.clone();
+        let handle = load_font(world.resource::<AssetServer>(), name.as_deref());
+        Self { name, handle }
+    }
+}
+
+/// Font files in the custom font directory, sorted by name.
+pub fn list_custom_fonts() -> Vec<String> {
+    list_files(
+        Path::new("assets").join(CUSTOM_FONT_DIRECTORY),
+        &["ttf", "otf"],
+    )
+}
+
+/// Loads the custom font `name` if it exists, the built-in font otherwise.
+fn load_font(asset_server: &AssetServer, name: Option<&str>) -> Handle<Font> {
+    if let Some(name) = name {
+        let custom = format!("{CUSTOM_FONT_DIRECTORY}/{name}");
+        if Path::new("assets").join(&custom).is_file() {
+            return asset_server.load(custom);
+        }
+        warn!("could not find font {custom}, using the built-in one");
+    }
+    asset_server.load(DEFAULT_FONT)
+}
+
+/// Swaps the font of all texts when another one is chosen in the options.
+pub fn update_game_font(
+    settings: Res<Settings>,
+    mut game_font: ResMut<GameFont>,
+    mut texts: Query<&mut TextFont>,
+    asset_server: Res<AssetServer>,
+) {
+    if game_font.name == settings.font {
+        return;
+    }
+    let handle = load_font(&asset_server, settings.font.as_deref());
+    replace_font(&mut texts, &game_font.handle, &handle);
+    *game_font = GameFont {
+        name: settings.font.clone(),
+        handle,
+    };
+}
+
+/// Replaces a custom font that couldn't be loaded with the built-in one.
+pub fn fall_back_to_default_font(
+    mut events: EventReader<AssetLoadFailedEvent<Font>>,
+    mut game_font: ResMut<GameFont>,
+    mut texts: Query<&mut TextFont>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in events.read() {
+        if event.id != game_font.handle.id() || game_font.name.is_none() {
+            continue;
+        }
+        warn!(
+            "could not load font {}, using the built-in one: {}",
+            event.path, event.error
+        );
+        let handle = asset_server.load(DEFAULT_FONT);
+        replace_font(&mut texts, &game_font.handle, &handle);
+        game_font.handle = handle;
+    }
+}
+
+fn replace_font(texts: &mut Query<&mut TextFont>, old: &Handle<Font>, new: &Handle<Font>) {
+    for mut text in texts.iter_mut() {
+        if text.font == *old {
+            text.font = new.clone();
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use crate::board::Board;
+use crate::grid::Occupancy;
+use crate::settings::Settings;
+use crate::{AppleValue, Constants, Custom, GameRng, choose_apple_spawn_cell};
+use bevy::prelude::*;
+
+/// Extra apple of a frenzy. It scores and grows the snake like the regular apple, but isn't
+/// replaced once eaten and disappears when the frenzy ends.
+#[derive(Component)]
+pub struct FrenzyApple;
+
+/// Counts down to the next frenzy while none is active, and the rest of the frenzy while one
+/// is. The countdown only starts once frenzies are turned on.
+#[derive(Resource, Default)]
+pub struct EventTimer {
+    timer: Option<Timer>,
+    active: bool,
+}
+
+/// Floods the board with extra apples every `frenzy_interval` seconds, and removes the ones left
+/// over once the frenzy is over.
+#[allow(clippy::too_many_arguments)]
+pub fn tick_frenzy(
+    mut commands: Commands,
+    mut event_timer: ResMut<EventTimer>,
+    mut rng: ResMut<GameRng>,
+    settings: Res<Settings>,
+    constants: Res<Constants>,
+    board: Res<Board>,
+    occupancy: Occupancy,
+    frenzy_apples: Query<Entity, With<FrenzyApple>>,
+    time: Res<Time>,
+) {
+    let Some(interval) = settings.frenzy_interval else {
+        // Turning frenzies off in the middle of one ends it right away.
+        for apple in frenzy_apples.iter() {
+            commands.entity(apple).despawn();
+        }
+        *event_timer = EventTimer::default();
+        return;
+    };
+    let timer = event_timer
+        .timer
+        .get_or_insert_with(|| Timer::from_seconds(interval, TimerMode::Once));
+    if !timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    if event_timer.active {
+        for apple in frenzy_apples.iter() {
+            commands.entity(apple).despawn();
+        }
+        *event_timer = EventTimer {
+            timer: Some(Timer::from_seconds(interval, TimerMode::Once)),
+            active: false,
+        };
+        return;
+    }
+
+    let mut occupied = occupancy.cells();
+    for _ in 0..settings.frenzy_apples {
+        let Some(cell) = choose_apple_spawn_cell(&board, &occupied, &mut rng.0) else {
+            break;
+        };
+        occupied.insert(cell);
+        commands.spawn((
+            Custom,
+            FrenzyApple,
+            AppleValue(settings.frenzy_apple_value),
+            cell,
+            Sprite {
+                color: Color::srgb(1.0, 0.85, 0.4),
+                ..Sprite::from_image(constants.apple_texture_handle.clone())
+            },
+            Transform::from_translation(board.cell_to_world(cell).extend(-1.0)),
+        ));
+    }
+    *event_timer = EventTimer {
+        timer: Some(Timer::from_seconds(
+            settings.frenzy_duration,
+            TimerMode::Once,
+        )),
+        active: true,
+    };
+}
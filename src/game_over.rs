@@ -0,0 +1,142 @@
+use crate::achievements::RunStats;
+use crate::fonts::GameFont;
+use crate::objective::Objective;
+use crate::settings::Settings;
+use crate::{Custom, GameState, Score};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::time::Duration;
+
+const LINE_SPACING: f32 = 45.0;
+
+/// Time left until the overlay accepts keys. Keys pressed right after the fatal move are
+/// ignored, so steering into a wall doesn't skip the game over screen.
+#[derive(Component)]
+pub struct GameOverOverlay(Timer);
+
+pub fn enter_game_over(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::GameOver);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_game_over_overlay(
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    score: Query<&Score>,
+    run_stats: Res<RunStats>,
+    objective: Res<Objective>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    let score = score.single().0;
+    let summary = if run_stats.is_new_high_score(score) {
+        format!("Score: {score}    New high score!")
+    } else {
+        format!("Score: {score}")
+    };
+
+    let title = if objective.is_won() {
+        String::from("You win!")
+    } else {
+        String::from("Game over")
+    };
+
+    let mut lines = vec![(title, 50.0), (summary, 30.0)];
+    lines.extend(run_stats.summary(time.elapsed()).map(|line| (line, 25.0)));
+    lines.push((String::from("Press any key to restart    Esc: Menu"), 25.0));
+
+    let font = game_font.handle.clone();
+    commands
+        .spawn((
+            Custom,
+            GameOverOverlay(Timer::new(
+                Duration::from_millis(settings.game_over_delay),
+                TimerMode::Once,
+            )),
+            Mesh2d(meshes.add(Rectangle::from_size(window.single().resolution.size()))),
+            MeshMaterial2d(color_materials.add(Color::srgba(0., 0., 0., 0.8))),
+            Transform::from_xyz(0.0, 0.0, 5.0),
+        ))
+        .with_children(|parent| {
+            let line_count = lines.len();
+            for (index, (text, font_size)) in lines.into_iter().enumerate() {
+                // Centered around the middle of the screen.
+                let y = LINE_SPACING * ((line_count - 1) as f32 / 2.0 - index as f32);
+                parent.spawn((
+                    Text2d::new(text),
+                    Transform::from_xyz(0.0, y, 1.0),
+                    TextColor(Color::srgb(0.5, 1.0, 1.0)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+/// Restarts on the first key press after the input delay, Esc restarts into the pause menu.
+pub fn leave_game_over(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut overlay: Query<&mut GameOverOverlay>,
+    mut next_state: ResMut<NextState<GameState>>,
+    time: Res<Time<Real>>,
+) {
+    let mut overlay = overlay.single_mut();
+    if !overlay.0.tick(time.delta()).finished() {
+        return;
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Paused);
+    } else if keys.get_just_pressed().next().is_some() {
+        next_state.set(GameState::Running);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+
+    /// Presses a key and runs a frame, returning whether that restarts the game.
+    fn press_key(app: &mut App) -> bool {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.reset_all();
+        keys.press(KeyCode::Space);
+        app.update();
+        matches!(
+            app.world().resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::Running)
+        )
+    }
+
+    #[test]
+    fn keys_within_the_delay_are_ignored() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .insert_state(GameState::GameOver)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .add_systems(
+                Update,
+                leave_game_over.run_if(in_state(GameState::GameOver)),
+            );
+        app.world_mut().spawn(GameOverOverlay(Timer::new(
+            Duration::from_millis(500),
+            TimerMode::Once,
+        )));
+
+        // The first frame takes no time.
+        for frame in 0..5 {
+            assert!(!press_key(&mut app), "restarted in frame {frame}");
+        }
+        assert!(press_key(&mut app));
+    }
+}
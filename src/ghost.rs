@@ -0,0 +1,126 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::storage::{SaveStorage, saves};
+use crate::{BodyPart, Custom, Head, Score};
+use bevy::prelude::*;
+use bincode::{Decode, Encode, config};
+use std::io;
+
+/// Where the snake was after one step of a recorded run.
+#[derive(Encode, Decode, Debug, Clone, Copy)]
+struct RecordedStep {
+    head: (i32, i32),
+    length: u32,
+}
+
+/// Route of a run, step by step, to race against as a ghost.
+///
+/// Only the resulting positions are kept rather than the inputs, so the ghost replays the exact
+/// route even though the apples of the new run appear elsewhere.
+#[derive(Encode, Decode, Debug, Default, Clone)]
+pub struct Recording {
+    score: u32,
+    steps: Vec<RecordedStep>,
+}
+
+/// Route of the current run so far.
+#[derive(Resource, Default)]
+pub struct CurrentRecording(Recording);
+
+/// Best recorded run, replayed next to the current one at the same pace.
+#[derive(Resource, Default)]
+pub struct Ghost {
+    recording: Recording,
+    step: usize,
+}
+
+impl Ghost {
+    pub fn new(settings: &Settings) -> Self {
+        if !settings.show_ghost {
+            return Self::default();
+        }
+        let recording = load_best_recording().unwrap_or_else(|err| {
+            warn!("could not load the ghost run: {err}");
+            Recording::default()
+        });
+        Self { recording, step: 0 }
+    }
+}
+
+#[derive(Component)]
+pub struct GhostMark;
+
+fn load_best_recording() -> io::Result<Recording> {
+    let Some(content) = saves().read("ghost")? else {
+        return Ok(Recording::default());
+    };
+    Ok(bincode::decode_from_slice(&content, config::standard())
+        .map(|(recording, _)| recording)
+        .unwrap_or_else(|err| {
+            warn!("could not decode the ghost run, starting over: {err}");
+            Recording::default()
+        }))
+}
+
+pub fn record_ghost_step(
+    mut current: ResMut<CurrentRecording>,
+    head: Query<&Cell, With<Head>>,
+    parts: Query<(), With<BodyPart>>,
+) {
+    let head = head.single();
+    current.0.steps.push(RecordedStep {
+        head: (head.x, head.y),
+        length: parts.iter().count() as u32,
+    });
+}
+
+/// Keeps the run as the new ghost if it scored more than the recorded one.
+pub fn save_recording(mut current: ResMut<CurrentRecording>, score: Query<&Score>) {
+    current.0.score = score.single().0;
+    let best = load_best_recording().unwrap_or_default();
+    if current.0.score <= best.score {
+        return;
+    }
+    let encoded =
+        bincode::encode_to_vec(&current.0, config::standard()).expect("failed to encode run");
+    if let Err(err) = saves().write("ghost", &encoded) {
+        warn!("could not save the ghost run: {err}");
+    }
+}
+
+/// Draws the ghost as translucent squares where the recorded snake was after as many steps as
+/// the current run took. Once the recorded run is over, the ghost is gone.
+pub fn advance_ghost(
+    mut commands: Commands,
+    mut ghost: ResMut<Ghost>,
+    marks: Query<Entity, With<GhostMark>>,
+    board: Res<Board>,
+) {
+    for mark in marks.iter() {
+        commands.entity(mark).despawn();
+    }
+    let step = ghost.step;
+    ghost.step += 1;
+    let Some(current) = ghost.recording.steps.get(step) else {
+        return;
+    };
+
+    let first = (step + 1).saturating_sub(current.length as usize);
+    for recorded in &ghost.recording.steps[first..=step] {
+        let cell = Cell::new(recorded.head.0, recorded.head.1);
+        // The ghost may come from a bigger board.
+        if !board.contains(cell) {
+            continue;
+        }
+        commands.spawn((
+            Custom,
+            GhostMark,
+            Sprite::from_color(
+                Color::srgba(1.0, 1.0, 1.0, 0.25),
+                Vec2::splat(board.cell_size * 0.8),
+            ),
+            Transform::from_translation(board.cell_to_world(cell).extend(-1.2)),
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::settings::Settings;
+use crate::{NextBodyPart, Tail};
+use bevy::prelude::*;
+
+/// Brightness of the tail, the head is always drawn at full brightness.
+const TAIL_BRIGHTNESS: f32 = 0.45;
+
+/// Tints every body part by its position between tail and head, so the snake fades toward the
+/// tail. Parts shift along the chain each tick, so the tint is recomputed from scratch.
+pub fn update_body_gradient(
+    settings: Res<Settings>,
+    tail: Query<Entity, With<Tail>>,
+    mut parts: Query<(&mut Sprite, &NextBodyPart)>,
+) {
+    let mut chain = vec![];
+    let mut next = Some(tail.single());
+    while let Some(entity) = next {
+        chain.push(entity);
+        next = parts.get(entity).expect("expected linked body part").1.0;
+    }
+
+    let last = (chain.len() - 1).max(1) as f32;
+    for (index, entity) in chain.into_iter().enumerate() {
+        let (mut sprite, _) = parts.get_mut(entity).expect("expected linked body part");
+        sprite.color = if settings.body_gradient {
+            let brightness = TAIL_BRIGHTNESS + (1.0 - TAIL_BRIGHTNESS) * index as f32 / last;
+            Color::srgb(brightness, brightness, brightness)
+        } else {
+            Color::WHITE
+        };
+    }
+}
@@ -0,0 +1,81 @@
+use crate::frenzy::FrenzyApple;
+use crate::hazard_apple::HazardApple;
+use crate::path_hint::PathHintPickup;
+use crate::shield::ShieldPickup;
+use crate::shrink_apple::ShrinkApple;
+use crate::{Apple, BodyPart, Obstacle};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Logical position on the board, with the center cell at the origin.
+///
+/// This is the authoritative position for game logic, the `Transform` only follows it for
+/// rendering, see [`Board::cell_to_world`](crate::board::Board::cell_to_world).
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Deref)]
+pub struct Cell(pub IVec2);
+
+impl Cell {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Cell(IVec2::new(x, y))
+    }
+}
+
+/// Everything taking up a cell on the board.
+#[derive(SystemParam)]
+#[allow(clippy::type_complexity)]
+pub struct Occupancy<'w, 's> {
+    query: Query<
+        'w,
+        's,
+        &'static Cell,
+        Or<(
+            With<BodyPart>,
+            With<Apple>,
+            With<FrenzyApple>,
+            With<ShrinkApple>,
+            With<HazardApple>,
+            With<PathHintPickup>,
+            With<ShieldPickup>,
+            With<Obstacle>,
+        )>,
+    >,
+}
+
+impl Occupancy<'_, '_> {
+    pub fn cells(&self) -> HashSet<Cell> {
+        self.query.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn occupancy_covers_snake_apples_and_obstacles() {
+        let mut world = World::new();
+        world.spawn((BodyPart, Cell::new(0, 0)));
+        world.spawn((BodyPart, Cell::new(-1, 0)));
+        world.spawn((Apple, Cell::new(3, 3)));
+        world.spawn((ShrinkApple, Cell::new(-2, 4)));
+        world.spawn((Obstacle, Cell::new(5, -5)));
+        // Cells of anything else, like the marks of a path hint, don't take up the board.
+        world.spawn(Cell::new(1, 1));
+
+        let cells = world
+            .run_system_once(|occupancy: Occupancy| occupancy.cells())
+            .unwrap();
+        assert_eq!(
+            cells,
+            HashSet::from_iter([
+                Cell::new(0, 0),
+                Cell::new(-1, 0),
+                Cell::new(3, 3),
+                Cell::new(-2, 4),
+                Cell::new(5, -5),
+            ])
+        );
+    }
+}
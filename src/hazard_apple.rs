@@ -0,0 +1,85 @@
+use crate::board::Board;
+use crate::grid::{Cell, Occupancy};
+use crate::settings::Settings;
+use crate::{Constants, Custom, GameOverEvent, GameRng, Head, Score, choose_apple_spawn_cell};
+use bevy::prelude::*;
+use bincode::{Decode, Encode};
+use rand::Rng;
+
+/// Points taken away by a hazard apple with the [`HazardPenalty::LosePoints`] penalty.
+const HAZARD_POINTS: u32 = 5;
+
+/// Apple look-alike that is bad to eat, drawn with its own texture so it can't be mistaken
+/// for a regular apple.
+#[derive(Component)]
+pub struct HazardApple;
+
+/// What eating a hazard apple does.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HazardPenalty {
+    #[default]
+    EndRun,
+    LosePoints,
+}
+
+impl HazardPenalty {
+    const ALL: [HazardPenalty; 2] = [HazardPenalty::EndRun, HazardPenalty::LosePoints];
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+pub fn spawn_hazard_apple(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    settings: Res<Settings>,
+    constants: Res<Constants>,
+    board: Res<Board>,
+    occupancy: Occupancy,
+    hazard_apples: Query<(), With<HazardApple>>,
+) {
+    if !hazard_apples.is_empty() || !rng.0.random_bool(settings.hazard_apple_chance) {
+        return;
+    }
+    let Some(spawn_cell) = choose_apple_spawn_cell(&board, &occupancy.cells(), &mut rng.0) else {
+        return;
+    };
+
+    commands.spawn((
+        Custom,
+        HazardApple,
+        spawn_cell,
+        Sprite::from_image(constants.hazard_apple_texture_handle.clone()),
+        Transform::from_translation(board.cell_to_world(spawn_cell).extend(-1.0)),
+    ));
+}
+
+/// Applies the configured penalty instead of growing when the head reaches a hazard apple.
+pub fn eat_hazard_apple(
+    mut commands: Commands,
+    mut game_over_event: EventWriter<GameOverEvent>,
+    mut score: Query<(&mut Score, &mut Text)>,
+    head: Query<&Cell, With<Head>>,
+    hazard_apples: Query<(Entity, &Cell), With<HazardApple>>,
+    settings: Res<Settings>,
+) {
+    let head = head.single();
+    for (hazard_apple, cell) in hazard_apples.iter() {
+        if cell != head {
+            continue;
+        }
+        commands.entity(hazard_apple).despawn();
+        match settings.hazard_penalty {
+            HazardPenalty::EndRun => {
+                game_over_event.send(GameOverEvent);
+            }
+            HazardPenalty::LosePoints => {
+                let (mut score, mut text) = score.single_mut();
+                score.0 = score.0.saturating_sub(HAZARD_POINTS);
+                text.0 = format!("Score: {}", score.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::settings::Settings;
+use crate::storage::{SaveStorage, saves};
+use bevy::prelude::*;
+use bincode::{Decode, Encode, config};
+use std::collections::BTreeMap;
+use std::io;
+
+/// Modes that keep their own high score, as their scores aren't comparable.
+#[derive(Resource, Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GameMode {
+    #[default]
+    Endless,
+    SuddenDeath,
+    Zen,
+    /// The snake only moves when a direction is pressed.
+    Steps,
+    /// Doubled scores with a faster snake and solid walls.
+    Risk,
+}
+
+impl GameMode {
+    pub fn from_settings(settings: &Settings) -> Self {
+        if settings.step_mode {
+            GameMode::Steps
+        } else if settings.zen_mode {
+            GameMode::Zen
+        } else if settings.risk_mode {
+            GameMode::Risk
+        } else if settings.sudden_death.is_some() {
+            GameMode::SuddenDeath
+        } else {
+            GameMode::Endless
+        }
+    }
+
+    /// Label of the high score text, so it's clear which table it comes from.
+    pub fn high_score_label(self) -> &'static str {
+        match self {
+            GameMode::Endless => "Highest",
+            GameMode::SuddenDeath => "Highest (sudden death)",
+            GameMode::Zen => "Highest (zen)",
+            GameMode::Steps => "Highest (step by step)",
+            GameMode::Risk => "Highest (risk)",
+        }
+    }
+}
+
+#[derive(Resource, Encode, Decode, Debug, Default)]
+pub struct HighScores(BTreeMap<GameMode, u32>);
+
+impl HighScores {
+    pub fn get(&self, mode: GameMode) -> u32 {
+        self.0.get(&mode).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, mode: GameMode, score: u32) {
+        self.0.insert(mode, score);
+    }
+}
+
+pub fn load_high_scores() -> io::Result<HighScores> {
+    let Some(content) = saves().read("high_scores")? else {
+        return load_legacy_high_score();
+    };
+    Ok(bincode::decode_from_slice(&content, config::standard())
+        .map(|(high_scores, _)| high_scores)
+        .unwrap_or_else(|err| {
+            warn!("could not decode high scores, starting over: {err}");
+            HighScores::default()
+        }))
+}
+
+/// Reads the single high score saved before there were separate modes, it becomes the
+/// endless record.
+fn load_legacy_high_score() -> io::Result<HighScores> {
+    let Some(content) = saves().read("high_score")? else {
+        return Ok(HighScores::default());
+    };
+    Ok(bincode::decode_from_slice(&content, config::standard())
+        .map(|(score, _)| HighScores(BTreeMap::from([(GameMode::Endless, score)])))
+        .unwrap_or_else(|err| {
+            warn!("could not decode the old high score, starting over: {err}");
+            HighScores::default()
+        }))
+}
+
+pub fn save_high_scores(high_scores: &HighScores) -> io::Result<()> {
+    let encoded = bincode::encode_to_vec(high_scores, config::standard())
+        .expect("failed to encode high scores");
+    saves().write("high_scores", &encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_high_score_becomes_the_endless_record() {
+        let old = bincode::encode_to_vec(37u32, config::standard()).unwrap();
+        saves().write("high_score", &old).unwrap();
+
+        let high_scores = load_high_scores().unwrap();
+        assert_eq!(high_scores.get(GameMode::Endless), 37);
+        assert_eq!(high_scores.get(GameMode::Zen), 0);
+    }
+
+    #[test]
+    fn modes_keep_their_own_high_scores() {
+        let mut high_scores = HighScores::default();
+        high_scores.set(GameMode::Endless, 12);
+        high_scores.set(GameMode::Risk, 30);
+        save_high_scores(&high_scores).unwrap();
+        // Saved per mode, so the old single high score is ignored.
+        let old = bincode::encode_to_vec(99u32, config::standard()).unwrap();
+        saves().write("high_score", &old).unwrap();
+
+        let loaded = load_high_scores().unwrap();
+        assert_eq!(loaded.get(GameMode::Endless), 12);
+        assert_eq!(loaded.get(GameMode::Risk), 30);
+        assert_eq!(loaded.get(GameMode::SuddenDeath), 0);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::GameState;
+use crate::fonts::GameFont;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::text::TextLayoutInfo;
+use bevy::window::PrimaryWindow;
+
+const HOW_TO_PLAY: &str = "\
+How to Play
+
+Controls
+  W A S D / Arrow keys: Steer, rebind W A S D in the options
+  Mouse or touch swipe: Steer
+  Gamepad D-pad: Steer
+  Esc: Pause and resume
+  O (paused): Options
+  H (paused): How to play
+  E (paused): Scenario editor
+  B: Toggle rounded body
+  + / -: Zoom in and out
+  F12: Screenshot
+
+Custom textures
+  Put PNGs named like the ones in assets/textures into
+  assets/textures/custom to replace them.
+  Put TTF or OTF fonts into assets/fonts/custom to pick
+  them in the options.
+  Put WAV or OGG files into assets/sounds/crunch to
+  replace the crunch sounds.
+
+Scoring
+  Every apple scores a point and grows the snake, the
+  points per apple can be changed in the options.
+  Eat apples in quick succession to build a combo
+  multiplier, if enabled in the options.
+  Blue shrink apples remove two tail segments.
+  Dark apples with a cross are hazards, eating one ends
+  the run or costs points, see the options.
+  Light blue shields, if enabled in the options, save
+  the snake from its next deadly collision.
+  With an apple timeout, apples move if not eaten in time.
+  In sudden death mode every apple adds time to the
+  clock and the run ends when it runs out.
+
+Rules
+  Leaving the board wraps around to the opposite edge,
+  unless solid walls are on. With wall grace the snake
+  bounces off them a few times before they end the run.
+  With a wrap limit, wrapping too often speeds you up.
+  Running into yourself or an obstacle ends the run.
+  In mirror mode a second snake mirrors yours across the
+  middle of the board, the two must never touch.
+  Zen mode turns off deaths, the clock and the score.
+  Step by step, the snake moves one cell per pressed
+  direction and waits in between, with its own high score.
+  Risk mode doubles every score, but the snake is faster
+  and the walls are solid, it has its own high score too.
+  Pick a scenario saved in the editor in the options.
+
+W / S: Scroll    Esc: Back";
+
+const MARGIN: f32 = 20.0;
+const SCROLL_SPEED: f32 = 400.0;
+
+#[derive(Component)]
+pub struct HowToPlayPanel;
+
+#[derive(Component)]
+pub struct HowToPlayText;
+
+pub fn open_how_to_play(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::KeyH) {
+        next_state.set(GameState::HowToPlay);
+    }
+}
+
+pub fn spawn_how_to_play(
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let resolution = &window.single().resolution;
+    commands
+        .spawn((
+            HowToPlayPanel,
+            Mesh2d(meshes.add(Rectangle::from_size(resolution.size()))),
+            MeshMaterial2d(color_materials.add(Color::srgba(0.0, 0.0, 0.0, 0.9))),
+            Transform::from_xyz(0.0, 0.0, 5.0),
+        ))
+        .with_child((
+            HowToPlayText,
+            Text2d::new(HOW_TO_PLAY),
+            Anchor::TopCenter,
+            Transform::from_xyz(0.0, resolution.height() / 2.0 - MARGIN, 1.0),
+            TextColor(Color::srgb(0.5, 1.0, 1.0)),
+            TextFont {
+                font: game_font.handle.clone(),
+                font_size: 25.0,
+                ..default()
+            },
+        ));
+}
+
+pub fn navigate_how_to_play(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut query: Query<(&mut Transform, &TextLayoutInfo), With<HowToPlayText>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time<Real>>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Paused);
+        return;
+    }
+
+    let mut scroll = 0.0;
+    if keys.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+        scroll -= 1.0;
+    }
+    if keys.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+        scroll += 1.0;
+    }
+
+    // Only scroll as far as needed to reveal the last line in small windows.
+    let window = window.single();
+    let window_height = window.resolution.height();
+    let (mut transform, layout) = query.single_mut();
+    // The layout is measured in physical pixels.
+    let text_height = layout.size.y / window.scale_factor();
+    let top = window_height / 2.0 - MARGIN;
+    let bottom = top + (text_height - window_height + 2.0 * MARGIN).max(0.0);
+    transform.translation.y =
+        (transform.translation.y + scroll * SCROLL_SPEED * time.delta_secs()).clamp(top, bottom);
+}
@@ -0,0 +1,92 @@
+use crate::settings::Settings;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bincode::{Decode, Encode};
+
+/// Below this window width or height, the automatic layout switches to the compact one.
+const COMPACT_WINDOW_SIZE: Vec2 = Vec2::new(900.0, 700.0);
+const FULL_FONT_SIZE: f32 = 40.0;
+const COMPACT_FONT_SIZE: f32 = 22.0;
+
+/// Layout of the score readouts in the top left corner.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HudLayout {
+    /// Compact in small windows, full otherwise.
+    #[default]
+    Auto,
+    /// One readout per line in large text.
+    Full,
+    /// All readouts on a single line in small text, to keep the board clear in small windows.
+    Compact,
+}
+
+impl HudLayout {
+    const ALL: [HudLayout; 3] = [HudLayout::Auto, HudLayout::Full, HudLayout::Compact];
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|l| *l == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+
+    fn is_compact(self, window_size: Vec2) -> bool {
+        match self {
+            HudLayout::Auto => window_size.cmplt(COMPACT_WINDOW_SIZE).any(),
+            HudLayout::Full => false,
+            HudLayout::Compact => true,
+        }
+    }
+}
+
+/// Node holding the score readouts.
+#[derive(Component)]
+pub struct ScoreHud;
+
+/// Follows the chosen layout and the window size, only touching the nodes when the layout
+/// actually changes, so the UI isn't laid out again every frame.
+pub fn update_hud_layout(
+    settings: Res<Settings>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut hud: Query<(&mut Node, &Children), With<ScoreHud>>,
+    mut fonts: Query<&mut TextFont>,
+) {
+    let (Ok(window), Ok((mut node, children))) = (window.get_single(), hud.get_single_mut()) else {
+        return;
+    };
+    let (direction, gap, font_size) = if settings.hud_layout.is_compact(window.size()) {
+        (FlexDirection::Row, Val::Px(20.0), COMPACT_FONT_SIZE)
+    } else {
+        (FlexDirection::Column, Val::Px(0.0), FULL_FONT_SIZE)
+    };
+    if node.flex_direction == direction {
+        return;
+    }
+    node.flex_direction = direction;
+    node.column_gap = gap;
+    for child in children.iter() {
+        if let Ok(mut font) = fonts.get_mut(*child) {
+            font.font_size = font_size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALL_WINDOW: Vec2 = Vec2::new(640.0, 480.0);
+    const LARGE_WINDOW: Vec2 = Vec2::new(1280.0, 720.0);
+
+    #[test]
+    fn auto_layout_follows_the_window_size() {
+        assert!(HudLayout::Auto.is_compact(SMALL_WINDOW));
+        assert!(!HudLayout::Auto.is_compact(LARGE_WINDOW));
+    }
+
+    #[test]
+    fn chosen_layouts_ignore_the_window_size() {
+        for size in [SMALL_WINDOW, LARGE_WINDOW] {
+            assert!(!HudLayout::Full.is_compact(size));
+            assert!(HudLayout::Compact.is_compact(size));
+        }
+    }
+}
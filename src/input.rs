@@ -0,0 +1,98 @@
+use crate::key_bindings::{Action, KeyBindings};
+use bevy::prelude::*;
+use bincode::{Decode, Encode};
+
+/// Device a player steers with, so players sharing a computer don't steer each other's snake.
+///
+/// There is only one player so far, the mirrored snake copies its moves.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+    /// Keyboard and the first gamepad.
+    #[default]
+    Any,
+    /// The bound keys and the arrow keys.
+    Keyboard,
+    /// The first connected gamepad's D-pad.
+    Gamepad,
+}
+
+impl InputDevice {
+    const ALL: [InputDevice; 3] = [
+        InputDevice::Any,
+        InputDevice::Keyboard,
+        InputDevice::Gamepad,
+    ];
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|d| *d == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+/// Which key wins if keys of both axes are pressed within the same frame.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TurnPriority {
+    /// The key that turns the snake, keys along its current axis are ignored.
+    #[default]
+    Turning,
+    /// The first key in the order up, down, left, right, even if that one doesn't turn the
+    /// snake.
+    Fixed,
+}
+
+impl TurnPriority {
+    const ALL: [TurnPriority; 2] = [TurnPriority::Turning, TurnPriority::Fixed];
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+/// The gamepad the first player uses, the one connected first.
+pub fn first_gamepad<'a>(gamepads: &'a Query<(Entity, &Gamepad)>) -> Option<&'a Gamepad> {
+    gamepads
+        .iter()
+        .min_by_key(|(entity, _)| *entity)
+        .map(|(_, gamepad)| gamepad)
+}
+
+/// Sum of the directions newly pressed this frame on the device, only looking at the keyboard
+/// or gamepad if the device includes it.
+pub fn pressed_direction(
+    device: InputDevice,
+    keys: &ButtonInput<KeyCode>,
+    bindings: &KeyBindings,
+    gamepad: Option<&Gamepad>,
+) -> IVec2 {
+    let mut pressed_direction = IVec2::ZERO;
+    if device != InputDevice::Gamepad {
+        if keys.any_just_pressed([bindings.key(Action::Up), KeyCode::ArrowUp]) {
+            pressed_direction.y += 1;
+        }
+        if keys.any_just_pressed([bindings.key(Action::Down), KeyCode::ArrowDown]) {
+            pressed_direction.y -= 1;
+        }
+        if keys.any_just_pressed([bindings.key(Action::Left), KeyCode::ArrowLeft]) {
+            pressed_direction.x -= 1;
+        }
+        if keys.any_just_pressed([bindings.key(Action::Right), KeyCode::ArrowRight]) {
+            pressed_direction.x += 1;
+        }
+    }
+    if device != InputDevice::Keyboard
+        && let Some(gamepad) = gamepad
+    {
+        for (button, direction) in [
+            (GamepadButton::DPadUp, IVec2::Y),
+            (GamepadButton::DPadDown, IVec2::NEG_Y),
+            (GamepadButton::DPadLeft, IVec2::NEG_X),
+            (GamepadButton::DPadRight, IVec2::X),
+        ] {
+            if gamepad.just_pressed(button) {
+                pressed_direction += direction;
+            }
+        }
+    }
+    pressed_direction
+}
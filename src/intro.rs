@@ -0,0 +1,122 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::{BodyPart, Direction, GameState};
+use bevy::prelude::*;
+
+/// Seconds it takes the snake to slide onto the board.
+const INTRO_DURATION: f32 = 0.8;
+
+/// Slide of the snake onto the board at the start of a run, from `offset` behind its start
+/// cells.
+///
+/// Plays in [`GameState::Intro`], so no input, movement or collision happens until the snake
+/// is in place and the run starts.
+#[derive(Resource)]
+pub struct Intro {
+    timer: Timer,
+    offset: Vec2,
+}
+
+/// Sets up the intro if it is enabled, the snake comes in one board length behind its start
+/// cells, along the direction it starts moving in.
+///
+/// A run restarted into the pause menu starts without the intro.
+pub fn start_intro(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    direction: Query<&Direction>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+) {
+    if !settings.intro_animation || *state.get() != GameState::Running {
+        return;
+    }
+    let board_length = (2 * board.half_extent + 1) as f32 * board.cell_size;
+    commands.insert_resource(Intro {
+        timer: Timer::from_seconds(INTRO_DURATION, TimerMode::Once),
+        offset: -direction.single().to_ivec2().as_vec2() * board_length,
+    });
+    next_state.set(GameState::Intro);
+}
+
+/// Eases the snake onto its cells and hands over to the run once it arrived.
+///
+/// Game time is paused outside of a run, so the intro plays in real time.
+pub fn play_intro(
+    mut commands: Commands,
+    mut intro: ResMut<Intro>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut parts: Query<(&Cell, &mut Transform), With<BodyPart>>,
+    board: Res<Board>,
+    time: Res<Time<Real>>,
+) {
+    intro.timer.tick(time.delta());
+    let remaining = 1.0 - intro.timer.fraction();
+    // Ease out, so the snake slows down as it reaches its cells.
+    let offset = intro.offset * remaining.powi(3);
+    for (cell, mut transform) in parts.iter_mut() {
+        let position = board.cell_to_world(*cell) + offset;
+        transform.translation = position.extend(transform.translation.z);
+    }
+    if intro.timer.finished() {
+        commands.remove_resource::<Intro>();
+        next_state.set(GameState::Running);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pause_gameplay_time, resume_gameplay_time};
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn intro_hands_over_to_the_run() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_state::<GameState>()
+            .insert_resource(Settings {
+                intro_animation: true,
+                ..default()
+            })
+            .insert_resource(Board::new(15, 50.0))
+            .add_systems(OnEnter(GameState::Running), resume_gameplay_time)
+            .add_systems(OnExit(GameState::Running), pause_gameplay_time)
+            .add_systems(Startup, start_intro)
+            .add_systems(Update, play_intro.run_if(in_state(GameState::Intro)));
+        let part = app
+            .world_mut()
+            .spawn((
+                BodyPart,
+                Cell::new(2, 3),
+                Direction::Right,
+                Transform::default(),
+            ))
+            .id();
+
+        app.update();
+        assert_eq!(
+            *app.world().resource::<State<GameState>>().get(),
+            GameState::Intro
+        );
+        for _ in 0..20 {
+            app.update();
+        }
+
+        assert_eq!(
+            *app.world().resource::<State<GameState>>().get(),
+            GameState::Running
+        );
+        assert!(!app.world().contains_resource::<Intro>());
+        let board = app.world().resource::<Board>();
+        let translation = app.world().get::<Transform>(part).unwrap().translation;
+        assert_eq!(translation.truncate(), board.cell_to_world(Cell::new(2, 3)));
+    }
+}
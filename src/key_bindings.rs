@@ -0,0 +1,201 @@
+use bevy::prelude::*;
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+use std::fmt;
+
+/// Keys that steering can be bound to. The arrow keys always steer in addition, and the keys of
+/// the hotkeys available while running (B, F and U) are left out.
+const BINDABLE_KEYS: [KeyCode; 33] = [
+    KeyCode::KeyA,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Something the player can bind a key to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Action {
+    pub const ALL: [Action; 4] = [Action::Up, Action::Down, Action::Left, Action::Right];
+}
+
+/// Name of a key as printed on the keyboard, like `W` or `1`.
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingError {
+    /// The key already triggers another action.
+    Taken(KeyCode, Action),
+    /// The key is used by the game itself or can't be saved.
+    Unbindable(KeyCode),
+}
+
+impl fmt::Display for BindingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingError::Taken(key, action) => {
+                write!(f, "{} is already bound to {action:?}", key_name(*key))
+            }
+            BindingError::Unbindable(key) => write!(f, "{} can't be bound", key_name(*key)),
+        }
+    }
+}
+
+/// Key of every [`Action`], with each key bound to at most one action.
+///
+/// Keys are saved as their position in the list of bindable keys, so only those can be bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBindings([KeyCode; Action::ALL.len()]);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self([KeyCode::KeyW, KeyCode::KeyS, KeyCode::KeyA, KeyCode::KeyD])
+    }
+}
+
+impl KeyBindings {
+    pub fn key(&self, action: Action) -> KeyCode {
+        self.0[action as usize]
+    }
+
+    /// Binds the key to the action, unless it is already bound to another one.
+    pub fn assign(&mut self, action: Action, key: KeyCode) -> Result<(), BindingError> {
+        if !BINDABLE_KEYS.contains(&key) {
+            return Err(BindingError::Unbindable(key));
+        }
+        if let Some(other) = Action::ALL
+            .into_iter()
+            .find(|other| *other != action && self.key(*other) == key)
+        {
+            return Err(BindingError::Taken(key, other));
+        }
+        self.0[action as usize] = key;
+        Ok(())
+    }
+}
+
+impl Encode for KeyBindings {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        for key in self.0 {
+            let index = BINDABLE_KEYS
+                .iter()
+                .position(|bindable| *bindable == key)
+                .ok_or(EncodeError::Other("key can't be bound"))?;
+            (index as u8).encode(encoder)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Context> Decode<Context> for KeyBindings {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let mut keys = Self::default().0;
+        for i in 0..keys.len() {
+            let index = u8::decode(decoder)? as usize;
+            let key = *BINDABLE_KEYS
+                .get(index)
+                .ok_or(DecodeError::Other("unknown key"))?;
+            if keys[..i].contains(&key) {
+                return Err(DecodeError::Other("key bound twice"));
+            }
+            keys[i] = key;
+        }
+        Ok(Self(keys))
+    }
+}
+
+bincode::impl_borrow_decode!(KeyBindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bincode::config;
+
+    #[test]
+    fn rejects_a_key_bound_to_another_action() {
+        let mut bindings = KeyBindings::default();
+        assert_eq!(
+            bindings.assign(Action::Up, KeyCode::KeyS),
+            Err(BindingError::Taken(KeyCode::KeyS, Action::Down))
+        );
+        assert_eq!(bindings, KeyBindings::default());
+        // Assigning the key an action already has is no conflict.
+        assert_eq!(bindings.assign(Action::Up, KeyCode::KeyW), Ok(()));
+    }
+
+    #[test]
+    fn rejects_keys_the_game_uses() {
+        let mut bindings = KeyBindings::default();
+        for key in [KeyCode::KeyB, KeyCode::ArrowUp, KeyCode::Escape] {
+            assert_eq!(
+                bindings.assign(Action::Left, key),
+                Err(BindingError::Unbindable(key))
+            );
+        }
+        assert_eq!(bindings, KeyBindings::default());
+    }
+
+    #[test]
+    fn bindings_round_trip_through_the_save() {
+        let mut bindings = KeyBindings::default();
+        bindings.assign(Action::Up, KeyCode::KeyI).unwrap();
+        bindings.assign(Action::Down, KeyCode::KeyK).unwrap();
+        bindings.assign(Action::Left, KeyCode::Digit0).unwrap();
+
+        let encoded = bincode::encode_to_vec(bindings, config::standard()).unwrap();
+        let (decoded, _) =
+            bincode::decode_from_slice::<KeyBindings, _>(&encoded, config::standard()).unwrap();
+        assert_eq!(decoded, bindings);
+    }
+
+    #[test]
+    fn rejects_saves_binding_a_key_twice() {
+        let encoded = bincode::encode_to_vec([0u8, 0, 1, 2], config::standard()).unwrap();
+        assert!(
+            bincode::decode_from_slice::<KeyBindings, _>(&encoded, config::standard()).is_err()
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use crate::BodyPart;
+use crate::settings::Settings;
+use bevy::prelude::*;
+
+/// Tints of the tiers after the first, which keeps the plain body. Once the snake is past the
+/// last one, it stays there.
+const TIER_COLORS: [Color; 4] = [
+    Color::srgb(1.0, 0.85, 0.3),
+    Color::srgb(1.0, 0.55, 0.2),
+    Color::srgb(0.95, 0.25, 0.25),
+    Color::srgb(0.7, 0.3, 1.0),
+];
+/// How much the tier color shows over the rest of the tint.
+const TIER_STRENGTH: f32 = 0.5;
+
+/// Tier reached with `length` parts, 0 being the plain body and every `tier_length` parts
+/// moving one tier up.
+fn length_tier(length: usize, tier_length: u32) -> usize {
+    (length / tier_length.max(1) as usize).min(TIER_COLORS.len())
+}
+
+/// Shifts the color of the whole body every `length_tiers` parts on top of the body gradient,
+/// so it's visible at a glance how far the snake has grown.
+pub fn tint_length_tier(settings: Res<Settings>, mut parts: Query<&mut Sprite, With<BodyPart>>) {
+    let Some(tier_length) = settings.length_tiers else {
+        return;
+    };
+    let tier = length_tier(parts.iter().count(), tier_length);
+    if tier == 0 {
+        return;
+    }
+    let color = TIER_COLORS[tier - 1];
+    for mut sprite in parts.iter_mut() {
+        sprite.color = sprite.color.mix(&color, TIER_STRENGTH);
+    }
+}
@@ -1,16 +1,185 @@
+mod achievements;
+mod apple_hint;
+mod apple_pips;
+mod apple_placement;
+mod apple_timeout;
+mod auto_save;
+mod background_pause;
+mod benchmark;
+mod blocked_turn;
+mod board;
+mod board_preview;
+mod camera;
+mod chain_links;
+mod closing_walls;
+mod collision;
+mod combo;
+mod console;
+mod crt;
+mod cursor_coordinates;
+mod danger_zone;
+mod digestion;
+mod ease_in;
+mod editor;
+mod files;
+mod fill_board;
+mod fonts;
+mod frenzy;
+mod game_over;
+mod ghost;
+mod gradient;
+mod grid;
+mod hazard_apple;
+mod high_scores;
+mod how_to_play;
+mod hud;
+mod input;
+mod intro;
+mod key_bindings;
+mod length_tiers;
+mod mirror;
+mod missing_assets;
+mod monochrome;
+mod objective;
+mod options;
+mod path_hint;
+mod risk_mode;
+mod rounded_body;
+mod route_bonus;
+mod scenario;
+mod screenshot;
+mod settings;
+mod shield;
+mod shrink_apple;
+mod sounds;
+mod speedometer;
+mod speedrun;
+mod start_grace;
+mod state_dump;
+mod step_mode;
+mod storage;
+mod sudden_death;
+mod swipe;
+mod textures;
+mod trail;
+mod undo;
+mod wall_grace;
+mod wall_pulse;
+mod worm;
+mod wrap_cooldown;
+mod wrap_flash;
+mod zen;
+
+use achievements::{
+    RunStats, check_achievements, expire_achievement_toasts, load_achievements, record_run_apple,
+    record_run_length, record_run_wrap,
+};
+use apple_hint::{flash_new_apples, shrink_apple_flashes, spawn_apple_arrow, update_apple_arrow};
+use apple_pips::{fill_apple_pips, spawn_apple_pips};
+use apple_placement::{ApplePlacement, UniformPlacement};
+use apple_timeout::{AppleAge, relocate_stale_apples};
+use auto_save::{AutoSave, auto_save};
+use background_pause::pause_in_background;
+use benchmark::{
+    BENCHMARK_SEED, Benchmark, record_benchmark_frame, restart_benchmark_run, steer_autopilot,
+    step_benchmark,
+};
+use bevy::asset::AssetLoadFailedEvent;
 use bevy::prelude::*;
-use bevy::sprite::Anchor;
+use bevy::sprite::{Anchor, Material2dPlugin};
 use bevy::utils::{HashMap, HashSet};
 use bevy::window::PrimaryWindow;
-use bincode::{Decode, Encode, config};
-use rand::prelude::{IndexedRandom, SliceRandom};
-use std::fs::File;
-use std::io::{ErrorKind, Read, Write};
-use std::path::Path;
-use std::time::Duration;
-use std::{fs, io};
-
-#[derive(PartialEq, Eq, Hash)]
+use bincode::{Decode, Encode};
+use blocked_turn::{BlockedTurnEvent, BlockedTurnFlash, flash_blocked_turn};
+use board::{Board, spawn_board};
+use board_preview::draw_board_preview;
+use camera::{center_camera, change_zoom, follow_head, hud_layer, spawn_cameras, zoom_camera};
+use chain_links::{ShowChainLinks, draw_chain_links};
+use closing_walls::{ClosingWalls, close_in_walls};
+use collision::{CollisionRules, SpawnInvulnerability, tick_spawn_invulnerability};
+use combo::{Combo, ComboText, expire_combo, update_combo_text};
+use console::{
+    Console, ConsolePanel, is_console_enabled, open_console, run_console_command, spawn_console,
+    type_in_console, update_console_text,
+};
+use crt::{CrtMaterial, spawn_crt_overlay, update_crt_overlay};
+use cursor_coordinates::{
+    CursorCoordinates, spawn_cursor_coordinates_text, update_cursor_coordinates,
+};
+use danger_zone::{direction_changed, update_danger_zone};
+use digestion::{start_digesting, tint_digesting_parts};
+use ease_in::{StartRamp, ease_in_movement};
+use editor::{
+    EditorLayout, despawn_editor, edit_layout, open_editor, spawn_editor, update_editor_marks,
+};
+use fill_board::fill_board;
+use fonts::{GameFont, fall_back_to_default_font, update_game_font};
+use frenzy::{EventTimer, FrenzyApple, tick_frenzy};
+use game_over::{enter_game_over, leave_game_over, spawn_game_over_overlay};
+use ghost::{CurrentRecording, Ghost, advance_ghost, record_ghost_step, save_recording};
+use gradient::update_body_gradient;
+use grid::{Cell, Occupancy};
+use hazard_apple::{eat_hazard_apple, spawn_hazard_apple};
+use high_scores::{GameMode, HighScores, load_high_scores, save_high_scores};
+use how_to_play::{HowToPlayPanel, navigate_how_to_play, open_how_to_play, spawn_how_to_play};
+use hud::{ScoreHud, update_hud_layout};
+use input::{TurnPriority, first_gamepad, pressed_direction};
+use intro::{play_intro, start_intro};
+use length_tiers::tint_length_tier;
+use mirror::{MIRROR_START_OFFSET, MirrorMode, mirror_cell, update_mirror_snake};
+use missing_assets::{
+    MissingAssets, record_missing_assets, spawn_missing_assets_text, update_missing_assets_text,
+};
+use monochrome::{
+    Monochrome, apply_monochrome_clear_color, apply_monochrome_materials, apply_monochrome_sprites,
+    is_monochrome,
+};
+use objective::{Objective, advance_objective, spawn_objective_text, update_objective_text};
+use options::{
+    KeyRemap, OptionsCursor, OptionsMenu, navigate_options, open_options, spawn_options_menu,
+    update_clear_color, update_msaa, update_options_text, update_present_mode,
+};
+use path_hint::{PathHint, eat_path_hint_pickup, spawn_path_hint_pickup, update_path_hint};
+use rand::SeedableRng;
+use rand::prelude::IndexedRandom;
+use rand::rngs::StdRng;
+use risk_mode::RiskMode;
+use rounded_body::{spawn_rounded_body, toggle_body_style, update_rounded_body};
+use route_bonus::{RouteBonus, RouteBonusText, count_route_step, update_route_bonus_text};
+use scenario::{
+    Scenario, expire_scenario_warnings, load_selected_scenario, placeable_obstacles,
+    spawn_obstacle, spawn_scenario_warning,
+};
+use screenshot::{expire_screenshot_notices, take_screenshot};
+use settings::{FrameSync, Settings, load_settings};
+use shield::{
+    Shield, eat_shield_pickup, spawn_shield_aura, spawn_shield_pickup, update_shield_aura,
+};
+use shrink_apple::{ShrinkAppleEatenEvent, eat_shrink_apple, shrink, spawn_shrink_apple};
+use sounds::load_crunch_sounds;
+use speedometer::{spawn_speedometer, update_speedometer};
+use speedrun::{
+    SpeedrunTimer, spawn_speedrun_text, tick_speedrun_timer, update_speedrun_text,
+    update_speedrun_visibility,
+};
+use start_grace::{StartGrace, spawn_start_grace_text, tick_start_grace, update_start_grace_text};
+use step_mode::{StepMode, is_step_mode, step_on_input};
+use sudden_death::{
+    SuddenDeath, SuddenDeathText, count_down_sudden_death, extend_sudden_death,
+    update_sudden_death_text,
+};
+use swipe::{SwipeStart, steer_by_swipe};
+use textures::{fall_back_to_builtin_textures, load_texture};
+use trail::{Trail, update_trail};
+use undo::{UndoHistory, is_debug_build, record_step, undo_step};
+use wall_grace::{WallHits, bounce_direction, expire_wall_warnings, spawn_wall_warning};
+use wall_pulse::pulse_walls;
+use worm::spring_body_parts;
+use wrap_cooldown::{WrapCounter, penalize_edge_camping};
+use wrap_flash::{fade_wrap_flashes, spawn_wrap_flashes};
+use zen::{animate_apples, update_score_visibility};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 enum SnakePart {
     Head,
     Body,
@@ -21,9 +190,45 @@ enum SnakePart {
 
 #[derive(Resource)]
 struct Constants {
-    size: f32,
     apple_texture_handle: Handle<Image>,
+    hazard_apple_texture_handle: Handle<Image>,
     snake_texture_handles: HashMap<SnakePart, Handle<Image>>,
+    /// Point of each snake texture that lies on the cell center and that rotations pivot around.
+    /// Only differs from the center for textures whose artwork isn't centered in the image.
+    snake_anchors: HashMap<SnakePart, Anchor>,
+}
+
+impl Constants {
+    fn part_sprite(&self, part: SnakePart) -> Sprite {
+        Sprite {
+            image: self.snake_texture_handles[&part].clone(),
+            anchor: self.snake_anchors.get(&part).copied().unwrap_or_default(),
+            ..default()
+        }
+    }
+
+    /// Like [`Constants::part_sprite`], mirrored vertically for turns the texture is drawn the
+    /// other way round for. The anchor is mirrored along, so it stays on the artwork.
+    fn flipped_part_sprite(&self, part: SnakePart, flip_y: bool) -> Sprite {
+        let mut sprite = self.part_sprite(part);
+        if flip_y {
+            sprite.flip_y = true;
+            sprite.anchor = Anchor::Custom(sprite.anchor.as_vec() * Vec2::new(1.0, -1.0));
+        }
+        sprite
+    }
+}
+
+/// Anchors of the snake textures, all pointing right. Only the head's artwork is off center,
+/// it sits a pixel above the middle of its 50 pixel image.
+fn snake_anchors() -> HashMap<SnakePart, Anchor> {
+    HashMap::from([
+        (SnakePart::Head, Anchor::Custom(Vec2::new(0.0, 1.0 / 50.0))),
+        (SnakePart::Body, Anchor::Center),
+        (SnakePart::BodyBent, Anchor::Center),
+        (SnakePart::BodyBent2, Anchor::Center),
+        (SnakePart::Tail, Anchor::Center),
+    ])
 }
 
 #[derive(Resource)]
@@ -31,6 +236,32 @@ struct AppleCrunch {
     handles: Vec<Handle<AudioSource>>,
 }
 
+/// Randomness affecting gameplay, seeded from `SNAKE_SEED` if set to make runs reproducible.
+#[derive(Resource)]
+struct GameRng(StdRng);
+
+impl GameRng {
+    fn from_seed(seed: Seed) -> Self {
+        Self(StdRng::seed_from_u64(seed.0))
+    }
+}
+
+/// Seed the [`GameRng`] started from, so a session can be reproduced with `SNAKE_SEED`.
+#[derive(Resource, Debug, Clone, Copy)]
+struct Seed(u64);
+
+impl Seed {
+    /// Falls back to `default_seed` if `SNAKE_SEED` isn't set, or to a random seed if that is
+    /// `None` as well.
+    fn from_env(default_seed: Option<u64>) -> Self {
+        match std::env::var("SNAKE_SEED").map(|seed| seed.parse::<u64>()) {
+            Ok(Ok(seed)) => Seed(seed),
+            Ok(Err(err)) => panic!("SNAKE_SEED must be a number: {err}"),
+            Err(_) => Seed(default_seed.unwrap_or_else(rand::random)),
+        }
+    }
+}
+
 #[derive(Event)]
 struct MovementEvent;
 
@@ -40,57 +271,500 @@ struct AppleEatenEvent(Entity);
 #[derive(Event)]
 struct GameOverEvent;
 
+/// Sent when the head leaves the board on one edge and re-enters on the opposite one.
+#[derive(Event)]
+struct WrapEvent {
+    /// Last cell before the edge it left through.
+    exit: Cell,
+    /// First cell after the opposite edge.
+    entry: Cell,
+    direction: IVec2,
+}
+
+/// Virtual time only advances while the game is [`GameState::Running`], so gameplay timers
+/// reading `Res<Time>` freeze on every other screen. Timers of UI and animations that should
+/// keep going outside of a run read `Res<Time<Real>>` instead.
+///
+/// Everything animated as part of the run reads `Res<Time>`, so the paused frame stays still:
+/// the breathing apples in zen mode, wall warnings and achievement toasts. Only the fade of the
+/// paused overlay, the menus, the intro and screenshot notices run on real time.
 #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
 enum GameState {
+    /// The snake slides onto the board before the run starts.
+    Intro,
     #[default]
     Running,
     Paused,
+    Options,
+    HowToPlay,
+    Editor,
+    Console,
+    GameOver,
 }
 
 fn main() {
-    App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: String::from("Snake"),
-                        ..default()
-                    }),
+    let benchmark = Benchmark::from_env();
+    let settings = if benchmark.is_some() {
+        Settings {
+            frame_sync: FrameSync::Unlimited,
+            // The benchmark keeps running while the window is in the background.
+            pause_in_background: false,
+            ..default()
+        }
+    } else {
+        load_settings().expect("could not read settings")
+    };
+    let default_seed = benchmark.as_ref().map(|_| BENCHMARK_SEED);
+    let seed = Seed::from_env(default_seed);
+    let mut app = App::new();
+    if let Some(benchmark) = benchmark {
+        app.insert_resource(benchmark);
+    }
+    app.add_plugins(
+        DefaultPlugins
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: String::from("Snake"),
+                    present_mode: settings.frame_sync.present_mode(),
                     ..default()
-                })
-                .set(ImagePlugin::default_nearest()),
+                }),
+                ..default()
+            })
+            .set(ImagePlugin::default_nearest()),
+    )
+    .add_plugins(Material2dPlugin::<CrtMaterial>::default())
+    .init_state::<GameState>()
+    .insert_resource(settings)
+    .insert_resource(GameRng::from_seed(seed))
+    .insert_resource(seed)
+    .insert_resource(load_achievements().expect("could not read achievements"))
+    .insert_resource(load_high_scores().expect("could not read high scores"))
+    .init_resource::<SwipeStart>()
+    .init_resource::<OptionsCursor>()
+    .init_resource::<KeyRemap>()
+    .init_resource::<Console>()
+    .init_resource::<CursorCoordinates>()
+    .init_resource::<ShowChainLinks>()
+    .init_resource::<MissingAssets>()
+    .init_resource::<GameFont>()
+    .add_event::<MovementEvent>()
+    .add_event::<AppleEatenEvent>()
+    .add_event::<BlockedTurnEvent>()
+    .init_resource::<BlockedTurnFlash>()
+    .add_event::<GameOverEvent>()
+    .add_event::<ShrinkAppleEatenEvent>()
+    .add_event::<WrapEvent>()
+    .add_systems(
+        Startup,
+        (setup, start_intro.after(setup), spawn_missing_assets_text),
+    )
+    .add_systems(
+        Update,
+        (
+            record_missing_assets::<Image>,
+            record_missing_assets::<Font>,
+            record_missing_assets::<AudioSource>,
+            record_missing_assets::<Shader>,
+            update_missing_assets_text
+                .after(record_missing_assets::<Image>)
+                .after(record_missing_assets::<Font>)
+                .after(record_missing_assets::<AudioSource>)
+                .after(record_missing_assets::<Shader>)
+                .run_if(resource_changed::<MissingAssets>),
+        ),
+    )
+    .add_systems(OnEnter(GameState::Running), resume_gameplay_time)
+    .add_systems(
+        OnExit(GameState::Running),
+        (pause_gameplay_time, center_camera),
+    )
+    .add_systems(
+        OnEnter(GameState::Paused),
+        (show_paused_overlay, update_paused_status),
+    )
+    .add_systems(
+        Update,
+        update_paused_status.run_if(in_state(GameState::Paused)),
+    )
+    .add_systems(OnExit(GameState::Paused), hide_paused_overlay)
+    .add_systems(
+        OnEnter(GameState::GameOver),
+        (
+            spawn_game_over_overlay,
+            save_recording.run_if(not(resource_exists::<Benchmark>)),
+        ),
+    )
+    .add_systems(
+        Update,
+        (
+            leave_game_over,
+            restart_benchmark_run.run_if(resource_exists::<Benchmark>),
+        )
+            .run_if(in_state(GameState::GameOver)),
+    )
+    .add_systems(
+        Update,
+        record_benchmark_frame.run_if(resource_exists::<Benchmark>),
+    )
+    .add_systems(
+        PostUpdate,
+        (
+            apply_monochrome_sprites,
+            apply_monochrome_materials,
+            apply_monochrome_clear_color,
+        )
+            .run_if(is_monochrome),
+    )
+    .add_systems(
+        OnExit(GameState::GameOver),
+        (
+            despawn_with::<Custom>,
+            setup,
+            update_score_visibility,
+            start_intro,
+        )
+            .chain(),
+    )
+    .add_systems(Update, play_intro.run_if(in_state(GameState::Intro)))
+    .add_systems(
+        Update,
+        auto_save
+            .after(check_game_over)
+            .run_if(in_state(GameState::Running)),
+    )
+    .add_systems(
+        Update,
+        (
+            toggle_pause_game,
+            pause_in_background,
+            toggle_body_style.run_if(not(in_state(GameState::Console))),
+            take_screenshot,
+            expire_screenshot_notices,
+            expire_achievement_toasts,
+            update_clear_color.run_if(resource_changed::<Settings>),
+            update_msaa.run_if(resource_changed::<Settings>),
+            update_crt_overlay.run_if(resource_changed::<Settings>),
+            update_speedrun_visibility.run_if(resource_changed::<Settings>),
+            update_present_mode.run_if(resource_changed::<Settings>),
+            fade_paused_overlay,
+            update_score_visibility.run_if(resource_changed::<Settings>),
+            update_hud_layout,
+            fall_back_to_builtin_textures.run_if(on_event::<AssetLoadFailedEvent<Image>>),
+            fall_back_to_default_font.run_if(on_event::<AssetLoadFailedEvent<Font>>),
+            update_game_font.run_if(resource_changed::<Settings>),
+        ),
+    )
+    .add_systems(
+        Update,
+        (open_options, open_how_to_play, open_editor).run_if(in_state(GameState::Paused)),
+    )
+    .add_systems(
+        OnEnter(GameState::Options),
+        (spawn_options_menu, draw_board_preview),
+    )
+    .add_systems(
+        Update,
+        (
+            navigate_options,
+            update_options_text.after(navigate_options),
+            draw_board_preview
+                .after(navigate_options)
+                .run_if(resource_changed::<Settings>),
+        )
+            .run_if(in_state(GameState::Options)),
+    )
+    .add_systems(OnExit(GameState::Options), despawn_with::<OptionsMenu>)
+    .add_systems(OnEnter(GameState::HowToPlay), spawn_how_to_play)
+    .add_systems(
+        Update,
+        navigate_how_to_play.run_if(in_state(GameState::HowToPlay)),
+    )
+    .add_systems(OnExit(GameState::HowToPlay), despawn_with::<HowToPlayPanel>)
+    .add_systems(OnEnter(GameState::Editor), spawn_editor)
+    .add_systems(
+        Update,
+        (
+            edit_layout,
+            update_editor_marks
+                .after(edit_layout)
+                .run_if(resource_exists_and_changed::<EditorLayout>),
+        )
+            .run_if(in_state(GameState::Editor)),
+    )
+    .add_systems(OnExit(GameState::Editor), despawn_editor)
+    .add_systems(
+        Update,
+        open_console
+            .run_if(in_state(GameState::Running).or(in_state(GameState::Paused)))
+            .run_if(is_console_enabled),
+    )
+    .add_systems(OnEnter(GameState::Console), spawn_console)
+    .add_systems(
+        Update,
+        (
+            type_in_console,
+            run_console_command.after(type_in_console),
+            update_console_text.after(run_console_command),
         )
-        .init_state::<GameState>()
-        .add_event::<MovementEvent>()
-        .add_event::<AppleEatenEvent>()
-        .add_event::<GameOverEvent>()
-        .add_systems(Startup, setup)
-        .add_systems(Update, toggle_pause_game)
-        .add_systems(
-            Update,
+            .run_if(in_state(GameState::Console)),
+    )
+    .add_systems(OnExit(GameState::Console), despawn_with::<ConsolePanel>)
+    .add_systems(
+        Startup,
+        spawn_cursor_coordinates_text
+            .after(setup)
+            .run_if(is_console_enabled),
+    )
+    .add_systems(
+        Update,
+        (update_cursor_coordinates, draw_chain_links).run_if(is_console_enabled),
+    )
+    .add_systems(
+        Update,
+        (
+            ease_in_movement
+                .after(tick_start_grace)
+                .before(trigger_movement),
+            trigger_movement
+                .run_if(not(resource_exists::<Benchmark>))
+                .run_if(not(is_step_mode)),
+            change_direction,
+            steer_by_swipe,
             (
-                trigger_movement,
-                change_direction,
-                (grow, update_score, play_crunch_sound).run_if(on_event::<AppleEatenEvent>),
+                grow,
+                spawn_shrink_apple.after(grow),
+                update_score.before(grow),
+                fill_apple_pips,
+                extend_sudden_death,
+                play_crunch_sound,
+                advance_objective,
+                update_objective_text.after(advance_objective),
+            )
+                // Only react once the step is fully applied, so a new apple never spawns on
+                // the cell the head is just moving into.
+                .after(check_game_over)
+                .run_if(on_event::<AppleEatenEvent>),
+            (
+                move_head.after(change_direction).after(steer_by_swipe),
                 (
-                    move_head.after(change_direction),
                     adjust_head_direction,
                     eat_apple,
-                    remove_tail.run_if(not(on_event::<AppleEatenEvent>)),
+                    eat_shrink_apple,
+                    eat_hazard_apple,
+                    remove_tail,
+                    shrink.run_if(on_event::<ShrinkAppleEatenEvent>),
                     adjust_tail_direction,
                     check_game_over,
                 )
                     .chain()
+                    .run_if(has_moved),
+            )
+                .chain()
+                .run_if(on_event::<MovementEvent>),
+            penalize_edge_camping
+                .after(move_head)
+                .run_if(on_event::<WrapEvent>),
+            update_trail
+                .after(move_head)
+                .run_if(on_event::<MovementEvent>),
+            count_down_sudden_death,
+            update_speedometer
+                .after(ease_in_movement)
+                .after(penalize_edge_camping),
+            relocate_stale_apples.after(grow),
+            (expire_wall_warnings, expire_scenario_warnings),
+            check_achievements
+                .after(update_score)
+                .run_if(not(resource_exists::<Benchmark>)),
+            animate_apples,
+            (
+                record_step
+                    .before(move_head)
                     .run_if(on_event::<MovementEvent>),
-                (despawn_all, setup)
-                    .chain()
-                    .run_if(on_event::<GameOverEvent>),
+                undo_step.after(check_game_over),
+                fill_board.after(check_game_over),
             )
-                .run_if(in_state(GameState::Running)),
+                .run_if(is_debug_build),
+            update_sudden_death_text
+                .after(count_down_sudden_death)
+                .after(extend_sudden_death)
+                .run_if(resource_changed::<SuddenDeath>),
+            expire_combo,
+            update_combo_text
+                .after(expire_combo)
+                .after(update_score)
+                .run_if(resource_changed::<Combo>),
+            (
+                step_benchmark,
+                steer_autopilot
+                    .after(change_direction)
+                    .after(steer_by_swipe)
+                    .before(move_head),
+            )
+                .run_if(resource_exists::<Benchmark>),
+            enter_game_over
+                .after(check_game_over)
+                .after(advance_objective)
+                .after(count_down_sudden_death)
+                .run_if(on_event::<GameOverEvent>),
+        )
+            .run_if(in_state(GameState::Running)),
+    )
+    .add_systems(
+        Update,
+        (
+            record_run_apple
+                .after(check_game_over)
+                .run_if(on_event::<AppleEatenEvent>),
+            record_run_length
+                .after(check_game_over)
+                .run_if(on_event::<MovementEvent>),
+            record_run_wrap
+                .after(move_head)
+                .run_if(on_event::<WrapEvent>),
+            count_route_step
+                .before(update_score)
+                .run_if(on_event::<MovementEvent>),
+            update_route_bonus_text.after(update_score),
+            record_ghost_step
+                .after(check_game_over)
+                .run_if(on_event::<MovementEvent>),
+            advance_ghost
+                .after(move_head)
+                .run_if(on_event::<MovementEvent>),
         )
-        .run();
+            .run_if(in_state(GameState::Running)),
+    )
+    .add_systems(
+        Update,
+        (
+            spawn_path_hint_pickup
+                .after(grow)
+                .after(spawn_shrink_apple)
+                .run_if(on_event::<AppleEatenEvent>),
+            eat_path_hint_pickup
+                .after(check_game_over)
+                .run_if(on_event::<MovementEvent>),
+            update_path_hint
+                .after(eat_path_hint_pickup)
+                .after(spawn_path_hint_pickup),
+            spawn_shield_pickup
+                .after(spawn_path_hint_pickup)
+                .run_if(on_event::<AppleEatenEvent>),
+            spawn_hazard_apple
+                .after(spawn_shield_pickup)
+                .run_if(on_event::<AppleEatenEvent>),
+            eat_shield_pickup
+                .after(check_game_over)
+                .run_if(on_event::<MovementEvent>),
+            update_shield_aura.after(eat_shield_pickup),
+            tick_frenzy.after(grow).after(spawn_path_hint_pickup),
+            close_in_walls
+                .after(tick_frenzy)
+                .after(check_game_over)
+                .before(enter_game_over),
+        )
+            .run_if(in_state(GameState::Running)),
+    )
+    .add_systems(
+        Update,
+        (
+            tick_speedrun_timer
+                .after(trigger_movement)
+                .after(step_benchmark)
+                .after(advance_objective),
+            update_speedrun_text.after(tick_speedrun_timer),
+            follow_head.after(check_game_over),
+            change_zoom,
+            zoom_camera.after(change_zoom),
+            tick_start_grace
+                .after(change_direction)
+                .after(steer_by_swipe)
+                .before(trigger_movement),
+            update_start_grace_text.after(tick_start_grace),
+            flash_blocked_turn
+                .after(change_direction)
+                .after(steer_by_swipe)
+                .after(update_body_gradient),
+            tick_spawn_invulnerability.before(move_head),
+            step_on_input
+                .after(change_direction)
+                .before(move_head)
+                .run_if(is_step_mode),
+            spawn_wrap_flashes
+                .after(move_head)
+                .run_if(on_event::<WrapEvent>),
+            fade_wrap_flashes,
+            spring_body_parts.after(check_game_over),
+            update_apple_arrow
+                .after(check_game_over)
+                .after(relocate_stale_apples),
+            flash_new_apples.after(grow),
+            shrink_apple_flashes,
+        )
+            .run_if(in_state(GameState::Running)),
+    )
+    .add_systems(
+        Update,
+        pulse_walls
+            .after(trigger_movement)
+            .after(step_benchmark)
+            .run_if(in_state(GameState::Running))
+            // The monochrome walls keep their one color.
+            .run_if(not(is_monochrome)),
+    )
+    .add_systems(
+        Update,
+        update_rounded_body
+            .after(check_game_over)
+            .after(play_intro)
+            .run_if(
+                on_event::<MovementEvent>
+                    .or(in_state(GameState::Intro))
+                    .or(resource_changed::<Settings>)
+                    .or(resource_changed::<Constants>),
+            ),
+    )
+    .add_systems(
+        Update,
+        (
+            update_body_gradient,
+            tint_length_tier.after(update_body_gradient),
+            start_digesting.run_if(on_event::<AppleEatenEvent>),
+            tint_digesting_parts
+                .after(tint_length_tier)
+                .after(start_digesting),
+        )
+            .after(check_game_over)
+            .run_if(on_event::<MovementEvent>.or(resource_changed::<Settings>)),
+    )
+    .add_systems(
+        Update,
+        update_mirror_snake
+            .after(check_game_over)
+            .after(tint_digesting_parts)
+            .run_if(on_event::<MovementEvent>.or(resource_changed::<MirrorMode>)),
+    )
+    .add_systems(
+        Update,
+        update_danger_zone
+            .after(check_game_over)
+            .after(change_direction)
+            .after(steer_by_swipe)
+            .run_if(
+                on_event::<MovementEvent>
+                    .or(resource_changed::<Settings>)
+                    .or(direction_changed),
+            ),
+    )
+    .run();
 }
 
+/// Tags everything belonging to the current run, which is despawned when restarting after a
+/// game over.
+///
+/// Screens spawned on entering a state tag their entities with a marker of their own instead,
+/// like [`OptionsMenu`], and despawn them with [`despawn_with`] on leaving the state.
 #[derive(Component)]
 struct Custom;
 
@@ -112,13 +786,21 @@ struct NextBodyPart(Option<Entity>);
 #[derive(Component)]
 struct Apple;
 
+/// Blocks a cell, running into it ends the run.
+#[derive(Component)]
+struct Obstacle;
+
+/// Points an apple scores before any multiplier, depending on its kind.
+#[derive(Component, Debug, Clone, Copy)]
+struct AppleValue(u32);
+
 #[derive(Component)]
 struct Score(u32);
 
-#[derive(Component, Encode, Decode)]
+#[derive(Component)]
 struct HighScore(u32);
 
-#[derive(Component, Debug, Default, Clone, PartialEq)]
+#[derive(Component, Encode, Decode, Debug, Default, Clone, PartialEq)]
 enum Direction {
     Up,
     Down,
@@ -138,6 +820,32 @@ impl Direction {
         )
     }
 
+    /// Direction of the step between two neighboring cells, also across the wrapped edges.
+    fn between(from: Cell, to: Cell) -> Option<Direction> {
+        // A step across an edge shows up as a jump to the opposite side of the board.
+        let step = (to.0 - from.0).map(|delta| match delta {
+            2.. => -1,
+            ..=-2 => 1,
+            _ => delta,
+        });
+        match step {
+            IVec2::Y => Some(Direction::Up),
+            IVec2::NEG_Y => Some(Direction::Down),
+            IVec2::NEG_X => Some(Direction::Left),
+            IVec2::X => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    fn to_ivec2(&self) -> IVec2 {
+        match self {
+            Direction::Up => IVec2::Y,
+            Direction::Down => IVec2::NEG_Y,
+            Direction::Left => IVec2::NEG_X,
+            Direction::Right => IVec2::X,
+        }
+    }
+
     fn to_radians(&self) -> f32 {
         f32::to_radians(match self {
             Direction::Up => 90.0,
@@ -154,208 +862,328 @@ struct LastDirection(Direction);
 #[derive(Component)]
 struct MovementTimer(Timer);
 
+/// Segments still to be added to the tail, one per step once `delay` more steps have passed.
+///
+/// Only segments that were actually added count for collisions, the snake doesn't get longer
+/// before they appear.
+#[derive(Resource, Default)]
+struct Growth {
+    segments: u32,
+    delay: u32,
+}
+
+/// Whether the head couldn't move on this step, because it crashed into a wall or had nowhere
+/// to turn away from one.
+///
+/// The rest of the step is skipped then, so the snake stays exactly as it was.
+#[derive(Resource, Default)]
+struct Stalled(bool);
+
+fn has_moved(stalled: Res<Stalled>) -> bool {
+    !stalled.0
+}
+
+/// Alpha of the paused overlay once it has fully faded in.
+const PAUSE_DIM: f32 = 0.8;
+const PAUSE_FADE_DURATION: f32 = 0.2;
+
+/// Dims the game while paused, `dimmed` is where the fade is heading.
 #[derive(Component)]
-struct PausedOverlay;
+struct PausedOverlay {
+    dimmed: bool,
+}
 
+#[derive(Component)]
+struct PausedStatus;
+
+#[allow(clippy::too_many_arguments)]
 fn setup(
     mut commands: Commands,
     window: Query<&Window, With<PrimaryWindow>>,
     asset_server: Res<AssetServer>,
+    game_font: Res<GameFont>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut color_materials: ResMut<Assets<ColorMaterial>>,
+    mut crt_materials: ResMut<Assets<CrtMaterial>>,
+    mut rng: ResMut<GameRng>,
+    settings: Res<Settings>,
+    high_scores: Res<HighScores>,
+    time: Res<Time>,
 ) {
-    let size = 50.0;
-    let speed = Duration::from_millis(100);
+    let risk_mode = RiskMode(settings.risk_mode);
+    let speed = risk_mode.base_interval(&settings);
+    let board = Board::from_settings(&settings, 50.0);
+    commands.insert_resource(board);
     let constants = Constants {
-        size,
         snake_texture_handles: HashMap::from([
-            (SnakePart::Head, asset_server.load("textures/head.png")),
-            (SnakePart::Body, asset_server.load("textures/body.png")),
+            (SnakePart::Head, load_texture(&asset_server, "head.png")),
+            (SnakePart::Body, load_texture(&asset_server, "body.png")),
             (
                 SnakePart::BodyBent,
-                asset_server.load("textures/body_bent.png"),
+                load_texture(&asset_server, "body_bent.png"),
             ),
             (
                 SnakePart::BodyBent2,
-                asset_server.load("textures/body_bent_2.png"),
+                load_texture(&asset_server, "body_bent_2.png"),
             ),
-            (SnakePart::Tail, asset_server.load("textures/tail.png")),
+            (SnakePart::Tail, load_texture(&asset_server, "tail.png")),
         ]),
-        apple_texture_handle: asset_server.load("textures/apple.png"),
+        snake_anchors: snake_anchors(),
+        apple_texture_handle: load_texture(&asset_server, "apple.png"),
+        hazard_apple_texture_handle: load_texture(&asset_server, "hazard_apple.png"),
     };
 
     commands.spawn((
         Custom,
         MovementTimer(Timer::new(speed, TimerMode::Repeating)),
     ));
+
+    let start_offset = if settings.mirror_mode {
+        MIRROR_START_OFFSET
+    } else {
+        IVec2::ZERO
+    };
+    let start_at =
+        |direction: &Direction| start_cells(direction).map(|cell| Cell(cell.0 + start_offset));
+    let mut scenario = load_selected_scenario(&settings);
+    if let Err(err) = scenario.validate(&board) {
+        warn!("can't start with the scenario, starting without it: {err}");
+        spawn_scenario_warning(
+            &mut commands,
+            window.single(),
+            game_font.handle.clone(),
+            &err,
+        );
+        scenario = Scenario::default();
+    }
+    if settings.mirror_mode && !scenario.snake.is_empty() {
+        warn!("the snake of the scenario can't be mirrored, starting the usual way");
+        scenario.snake.clear();
+    }
+
+    let mut start_direction = settings.start_direction.clone();
+    let start = if scenario.snake.is_empty() {
+        let mut start = start_at(&start_direction);
+        if !start.iter().all(|cell| board.contains(*cell)) {
+            warn!(
+                "snake doesn't fit on the board facing {start_direction:?}, starting to the right"
+            );
+            start_direction = Direction::default();
+            start = start_at(&start_direction);
+        }
+        // From tail to head.
+        start.into_iter().rev().collect::<Vec<_>>()
+    } else {
+        let cells = scenario.snake_cells().collect::<Vec<_>>();
+        start_direction = Direction::between(cells[cells.len() - 2], cells[cells.len() - 1])
+            .expect("expected a validated snake");
+        cells
+    };
     commands.spawn((
         Custom,
-        Direction::default(),
-        LastDirection(Direction::default()),
+        start_direction.clone(),
+        LastDirection(start_direction.clone()),
     ));
+    commands.insert_resource(MirrorMode(settings.mirror_mode));
+    commands.insert_resource(CollisionRules::from_settings(&settings));
 
-    let head_position = Vec2::default();
-    let head = spawn_part(
-        &mut commands,
-        Head,
-        head_position,
-        constants.snake_texture_handles[&SnakePart::Head].clone(),
-        NextBodyPart(None),
-    );
-    let body_position = Vec2::new(-size, 0.0);
-    let body = spawn_part(
-        &mut commands,
-        Body,
-        body_position,
-        constants.snake_texture_handles[&SnakePart::Body].clone(),
-        NextBodyPart(Some(head)),
-    );
-    let tail_position = Vec2::new(-2.0 * size, 0.0);
-    spawn_part(
-        &mut commands,
-        Tail,
-        tail_position,
-        constants.snake_texture_handles[&SnakePart::Tail].clone(),
-        NextBodyPart(Some(body)),
-    );
+    spawn_snake(&mut commands, &start, &board, &constants);
 
-    spawn_apple(
-        &mut commands,
-        size,
-        constants.apple_texture_handle.clone(),
-        vec![head_position, body_position, tail_position],
-    );
+    let mut occupied = start.iter().copied().collect::<HashSet<_>>();
+    for cell in placeable_obstacles(&scenario, &board, &start) {
+        spawn_obstacle(&mut commands, cell, &board);
+        occupied.insert(cell);
+    }
+    match scenario.apple_cell() {
+        Some(cell) if board.contains(cell) && !occupied.contains(&cell) => spawn_apple_at(
+            &mut commands,
+            cell,
+            &board,
+            constants.apple_texture_handle.clone(),
+            AppleValue(settings.apple_value),
+        ),
+        _ => spawn_apple(
+            &mut commands,
+            &mut rng,
+            &board,
+            constants.apple_texture_handle.clone(),
+            &occupied,
+            &settings,
+        ),
+    }
 
-    let font = asset_server.load("fonts/upheavtt.ttf");
+    let font = game_font.handle.clone();
     let resolution = &window.single().resolution;
-    commands.spawn((
-        Custom,
-        Score(0),
-        Text2d::new("Score: 0"),
-        TextFont {
-            font: font.clone(),
-            font_size: 40.0,
-            ..default()
-        },
-        Anchor::TopLeft,
-        Transform::from_translation(Vec3::new(
-            resolution.width() / -2.0 + 20.0,
-            resolution.height() / 2.0,
-            0.0,
-        )),
-    ));
+    let mode = GameMode::from_settings(&settings);
+    let high_score = HighScore(high_scores.get(mode));
+    commands.insert_resource(mode);
+    commands.insert_resource(RunStats::new(time.elapsed(), high_score.0));
+    let score_font = TextFont {
+        font: font.clone(),
+        font_size: 40.0,
+        ..default()
+    };
+    // UI nodes instead of world-space text, so the layout pins the scores to the top left corner
+    // and stacks them without overlap in any window size, including after resizing.
+    commands
+        .spawn((
+            Custom,
+            ScoreHud,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.0),
+                top: Val::Px(0.0),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((Score(0), Text::new("Score: 0"), score_font.clone()));
+            spawn_apple_pips(parent, constants.apple_texture_handle.clone());
+            parent.spawn((
+                Text::new(format!("{}: {}", mode.high_score_label(), high_score.0)),
+                high_score,
+                score_font.clone(),
+            ));
+            parent.spawn((
+                ComboText,
+                Text::default(),
+                TextColor(Color::srgb(1.0, 0.8, 0.2)),
+                score_font.clone(),
+                Visibility::Hidden,
+            ));
+            parent.spawn((
+                RouteBonusText,
+                Text::default(),
+                TextColor(Color::srgb(1.0, 0.8, 0.2)),
+                score_font,
+                Visibility::Hidden,
+            ));
+        });
+    commands.insert_resource(Combo::default());
+    commands.insert_resource(RouteBonus::default());
+    commands.insert_resource(Trail::default());
+    commands.insert_resource(UndoHistory::default());
+    commands.insert_resource(PathHint::default());
+    commands.insert_resource(Shield::default());
+    commands.insert_resource(EventTimer::default());
+    commands.insert_resource(ClosingWalls::default());
+    commands.insert_resource(AutoSave::default());
+    commands.insert_resource(CurrentRecording::default());
+    commands.insert_resource(Ghost::new(&settings));
 
-    let high_score = load_high_score().expect("could not read high score");
     commands.spawn((
         Custom,
-        Text2d::new(format!("Highest: {}", high_score.0)),
-        high_score,
+        SuddenDeathText,
+        hud_layer(),
+        Text2d::default(),
+        TextColor(Color::srgb(1.0, 0.3, 0.2)),
         TextFont {
             font: font.clone(),
-            font_size: 40.0,
+            font_size: 60.0,
             ..default()
         },
-        Anchor::TopLeft,
-        Transform::from_translation(Vec3::new(
-            resolution.width() / -2.0 + 20.0,
-            resolution.height() / 2.0 - 40.0,
-            0.0,
-        )),
+        Anchor::TopCenter,
+        Transform::from_translation(Vec3::new(0.0, resolution.height() / 2.0, 0.0)),
+        Visibility::Hidden,
     ));
+    commands.insert_resource(SuddenDeath::new(&settings));
 
-    commands.spawn((Custom, Camera2d));
+    spawn_shield_aura(&mut commands, &board);
+    spawn_speedometer(&mut commands, font.clone(), resolution);
+    spawn_start_grace_text(&mut commands, font.clone(), resolution);
+    spawn_apple_arrow(
+        &mut commands,
+        &mut meshes,
+        &mut color_materials,
+        font.clone(),
+    );
+    let objective = Objective::new(&settings);
+    spawn_objective_text(&mut commands, font.clone(), resolution, &objective);
+    commands.insert_resource(objective);
+    spawn_speedrun_text(&mut commands, font.clone(), resolution, &settings);
+    commands.insert_resource(SpeedrunTimer::default());
+
+    spawn_cameras(&mut commands, &settings);
+    commands.insert_resource(ClearColor(settings.clear_color()));
+    commands.insert_resource(Monochrome(settings.monochrome));
+    commands.insert_resource(StepMode(settings.step_mode));
+    commands.insert_resource(risk_mode);
     commands.insert_resource(constants);
-
-    let handles = (1..=4)
-        .map(|i| format!("sounds/apple-crunch-{i}.wav"))
-        .map(|name| asset_server.load(name))
-        .collect();
-    commands.insert_resource(AppleCrunch { handles });
+    commands.insert_resource(WrapCounter::default());
+    commands.insert_resource(WallHits::default());
+    commands.insert_resource(Stalled::default());
+    commands.insert_resource(Growth::default());
+    commands.insert_resource(StartRamp::new(&settings));
+    commands.insert_resource(StartGrace::new(&settings));
+    commands.insert_resource(SpawnInvulnerability::new(&settings));
+
+    commands.insert_resource(AppleCrunch {
+        handles: load_crunch_sounds(&asset_server),
+    });
 
     commands
         .spawn((
             Custom,
-            PausedOverlay,
+            PausedOverlay { dimmed: false },
             Mesh2d(meshes.add(Rectangle::from_size(resolution.size()))),
-            MeshMaterial2d(color_materials.add(Color::srgba(0., 0., 0., 0.8))),
+            MeshMaterial2d(color_materials.add(Color::srgba(0., 0., 0., 0.))),
             Visibility::Hidden,
         ))
-        .with_child((
-            Text2d::new("Paused"),
-            Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
-            TextColor(Color::srgb(0.5, 1.0, 1.0)),
-            TextFont {
-                font: font.clone(),
-                font_size: 50.0,
-                ..default()
-            },
-        ));
-
-    let playable_area = Vec2::splat(size * 13.0);
-    commands.spawn((
-        Custom,
-        Mesh2d(meshes.add(Rectangle::from_size(playable_area))),
-        MeshMaterial2d(color_materials.add(Color::srgb(0.1, 0.5, 0.3))),
-        Transform::from_xyz(0.0, 0.0, -2.0),
-    ));
-
-    let wall_color = color_materials.add(Color::srgb(0.3, 0.7, 0.6));
-    let horizontal_wall = meshes.add(Rectangle::new(size / 2.0, playable_area.y));
-    let vertical_wall = meshes.add(Rectangle::new(playable_area.x, size / 2.0));
-    commands.spawn((
-        Custom,
-        Mesh2d(horizontal_wall.clone()),
-        MeshMaterial2d(wall_color.clone()),
-        Transform::from_xyz(-6.5 * size, 0.0, 0.0),
-    ));
-    commands.spawn((
-        Custom,
-        Mesh2d(horizontal_wall),
-        MeshMaterial2d(wall_color.clone()),
-        Transform::from_xyz(6.5 * size, 0.0, 0.0),
-    ));
-    commands.spawn((
-        Custom,
-        Mesh2d(vertical_wall.clone()),
-        MeshMaterial2d(wall_color.clone()),
-        Transform::from_xyz(0.0, -6.5 * size, 0.0),
-    ));
-    commands.spawn((
-        Custom,
-        Mesh2d(vertical_wall),
-        MeshMaterial2d(wall_color),
-        Transform::from_xyz(0.0, 6.5 * size, 0.0),
-    ));
-}
-
-fn load_high_score() -> io::Result<HighScore> {
-    let file = File::open("assets/saves/high_score");
-    if let Err(err) = file {
-        match err.kind() {
-            ErrorKind::NotFound => Ok(HighScore(0)),
-            _ => Err(err),
-        }
-    } else {
-        let mut content = vec![];
-        file?.read_to_end(&mut content)?;
-        Ok(bincode::decode_from_slice(&content, config::standard())
-            .expect("failed to decode high score")
-            .0)
-    }
-}
-
-fn save_high_score(high_score: &HighScore) -> io::Result<()> {
-    let path = Path::new("assets/saves");
-    fs::create_dir_all(path)?;
-    let mut file = File::create(path.join("high_score"))?;
+        .with_children(|parent| {
+            parent.spawn((
+                Text2d::new("Paused"),
+                Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+                TextColor(Color::srgb(0.5, 1.0, 1.0)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 50.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text2d::new("O: Options    H: How to play    E: Editor"),
+                Transform::from_translation(Vec3::new(0.0, -50.0, 1.0)),
+                TextColor(Color::srgb(0.5, 1.0, 1.0)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 25.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                PausedStatus,
+                Text2d::default(),
+                Transform::from_translation(Vec3::new(0.0, -90.0, 1.0)),
+                TextColor(Color::srgb(0.5, 1.0, 1.0)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 20.0,
+                    ..default()
+                },
+            ));
+        });
 
-    let encoded = bincode::encode_to_vec(high_score, config::standard())
-        .expect("failed to encode high score");
-    file.write_all(&encoded)?;
+    spawn_rounded_body(&mut commands, &mut meshes, &mut color_materials);
+    spawn_crt_overlay(
+        &mut commands,
+        &mut meshes,
+        &mut crt_materials,
+        resolution,
+        &settings,
+    );
 
-    Ok(())
+    spawn_board(
+        &mut commands,
+        &mut meshes,
+        &mut color_materials,
+        &board,
+        settings.tunnel_walls,
+    );
 }
 
+/// Sends at most one [`MovementEvent`] per frame, even if the timer finished several times, so
+/// at any speed the head visits every cell on its way and can't skip over an apple.
 fn trigger_movement(
     mut query: Query<&mut MovementTimer>,
     mut movement_event: EventWriter<MovementEvent>,
@@ -366,34 +1194,91 @@ fn trigger_movement(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn move_head(
     mut commands: Commands,
-    mut query: Query<(&mut LastDirection, &Direction)>,
-    head_query: Query<(Entity, &Transform), With<Head>>,
+    mut query: Query<(&mut LastDirection, &mut Direction)>,
+    head_query: Query<(Entity, &Cell), With<Head>>,
+    body_parts: Query<&Cell, With<BodyPart>>,
     constants: Res<Constants>,
+    board: Res<Board>,
+    mut wrap_event: EventWriter<WrapEvent>,
+    mut game_over_event: EventWriter<GameOverEvent>,
+    mut wall_hits: ResMut<WallHits>,
+    settings: Res<Settings>,
+    collision_rules: Res<CollisionRules>,
+    spawn_invulnerability: Res<SpawnInvulnerability>,
+    mut shield: ResMut<Shield>,
+    mut stalled: ResMut<Stalled>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
 ) {
-    let size = constants.size;
-    let (mut last_direction, direction) = query.single_mut();
-    let (head, transform) = head_query.single();
-    let offset = Vec2::from(match direction {
-        Direction::Up => (0.0, size),
-        Direction::Down => (0.0, -size),
-        Direction::Left => (-size, 0.0),
-        Direction::Right => (size, 0.0),
-    });
-
-    let mut new_head_position = transform.translation.truncate() + offset;
-    let new_coordinates = (new_head_position / size + Vec2::splat(6.0) + 13.0) % 13.0;
-    new_head_position = (new_coordinates - Vec2::splat(6.0)) * size;
+    stalled.0 = false;
+    let (mut last_direction, mut direction) = query.single_mut();
+    let (head, head_cell) = head_query.single();
+
+    let unwrapped_head_cell = Cell(head_cell.0 + direction.to_ivec2());
+    let mut new_head_cell = board.wrap(unwrapped_head_cell);
+    if new_head_cell != unwrapped_head_cell {
+        wrap_event.send(WrapEvent {
+            exit: *head_cell,
+            entry: new_head_cell,
+            direction: direction.to_ivec2(),
+        });
+    }
+    if !board.contains(new_head_cell) {
+        // Ran into a solid wall, bounce off it while there is grace left or a shield to use up.
+        // Bounces right after spawning are free.
+        let blocked = body_parts.iter().copied().collect();
+        let bounce = bounce_direction(&board, *head_cell, &last_direction.0, &blocked);
+        let is_invulnerable = spawn_invulnerability.is_active();
+        let is_lethal =
+            collision_rules.walls && !is_invulnerable && wall_hits.0 >= settings.wall_grace;
+        match bounce {
+            Some(turn) if is_lethal && shield.0 => {
+                shield.0 = false;
+                new_head_cell = Cell(head_cell.0 + turn.to_ivec2());
+                *direction = turn;
+            }
+            Some(turn) if !is_lethal => {
+                if !is_invulnerable {
+                    wall_hits.0 += 1;
+                }
+                if collision_rules.walls && !is_invulnerable {
+                    spawn_wall_warning(
+                        &mut commands,
+                        window.single(),
+                        game_font.handle.clone(),
+                        settings.wall_grace - wall_hits.0,
+                    );
+                }
+                new_head_cell = Cell(head_cell.0 + turn.to_ivec2());
+                *direction = turn;
+            }
+            None if !is_lethal => {
+                // Nowhere to turn, wait against the wall until a turn frees the way.
+                stalled.0 = true;
+                return;
+            }
+            _ => {
+                stalled.0 = true;
+                game_over_event.send(GameOverEvent);
+                return;
+            }
+        }
+    }
+    let direction = direction.into_inner();
 
     let new_head = spawn_part(
         &mut commands,
         Head,
-        new_head_position,
-        constants.snake_texture_handles[&SnakePart::Head].clone(),
+        new_head_cell,
+        &board,
+        constants.part_sprite(SnakePart::Head),
+        Quat::IDENTITY,
         NextBodyPart(None),
     );
-    let is_clockwise = last_direction.0.is_clockwise(&direction);
+    let is_clockwise = last_direction.0.is_clockwise(direction);
     let part = if last_direction.0 == *direction {
         SnakePart::Body
     } else if is_clockwise {
@@ -401,15 +1286,12 @@ fn move_head(
     } else {
         SnakePart::BodyBent
     };
-    let mut sprite = Sprite::from_image(constants.snake_texture_handles[&part].clone());
-    sprite.flip_y = is_clockwise;
-
-    commands.entity(head).remove::<Head>().insert((
-        Body,
-        last_direction.0.clone(),
-        NextBodyPart(Some(new_head)),
-        sprite,
-    ));
+    let sprite = constants.flipped_part_sprite(part, is_clockwise);
+
+    commands
+        .entity(head)
+        .remove::<Head>()
+        .insert((Body, NextBodyPart(Some(new_head)), sprite));
     last_direction.0 = direction.clone();
 }
 
@@ -422,74 +1304,157 @@ fn adjust_head_direction(
 }
 
 fn adjust_tail_direction(
-    mut q_tail: Query<(&mut Transform, &NextBodyPart), With<Tail>>,
-    q_direction: Query<&Direction>,
+    mut q_tail: Query<(&mut Transform, &Cell, &NextBodyPart), With<Tail>>,
+    q_cell: Query<&Cell>,
 ) {
-    let (mut transform, next_body_part) = q_tail.single_mut();
-    if let Some(entity) = next_body_part.0 {
-        if let Ok(direction) = q_direction.get(entity) {
-            transform.rotation = Quat::IDENTITY;
-            transform.rotate_z(direction.to_radians());
-        }
+    let (mut transform, tail_cell, next_body_part) = q_tail.single_mut();
+    if let Some(entity) = next_body_part.0
+        && let Ok(next_cell) = q_cell.get(entity)
+        && let Some(direction) = Direction::between(*tail_cell, *next_cell)
+    {
+        transform.rotation = Quat::IDENTITY;
+        transform.rotate_z(direction.to_radians());
     }
 }
 
+/// Steers with the direction keys or D-pad buttons pressed this frame on the first player's
+/// input device.
+///
+/// Only newly pressed keys count, so holding or releasing keys and the keyboard's auto-repeat
+/// never turn the snake. All keys pressed within the same frame are combined and resolved by
+/// [`resolve_turn`].
+///
+/// A turn only updates the pending [`Direction`], which `move_head` applies on the next step.
+/// Movement is discrete, so the head always sits exactly on a cell center when it turns and a
+/// turn pressed early in a step is kept until then instead of being dropped.
 fn change_direction(
     mut query: Query<(&mut Direction, &LastDirection)>,
+    mut blocked_turn_event: EventWriter<BlockedTurnEvent>,
     keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    settings: Res<Settings>,
 ) {
     let (mut direction, last_direction) = query.single_mut();
 
-    let mut pressed_direction = Vec2::default();
-    if keys.any_just_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
-        pressed_direction.y += 1.0;
+    let pressed_direction = pressed_direction(
+        settings.player_one_input,
+        &keys,
+        &settings.key_bindings,
+        first_gamepad(&gamepads),
+    );
+
+    if steer(
+        &mut direction,
+        last_direction,
+        pressed_direction,
+        settings.strict_turns,
+        settings.turn_priority,
+    ) {
+        blocked_turn_event.send(BlockedTurnEvent);
     }
-    if keys.any_just_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
-        pressed_direction.y -= 1.0;
+}
+
+/// With `strict` turns, the first turn of a step is latched and any further presses until the
+/// next step are ignored. Otherwise the last press wins.
+///
+/// Returns whether the press was a reversal into the neck, which is always ignored.
+fn steer(
+    direction: &mut Direction,
+    last_direction: &LastDirection,
+    pressed_direction: IVec2,
+    strict: bool,
+    priority: TurnPriority,
+) -> bool {
+    if pressed_direction == -last_direction.0.to_ivec2() {
+        return true;
     }
-    if keys.any_just_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
-        pressed_direction.x -= 1.0;
+    if strict && *direction != last_direction.0 {
+        return false;
     }
-    if keys.any_just_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) {
-        pressed_direction.x += 1.0;
+    if let Some(turn) = resolve_turn(&last_direction.0, pressed_direction, priority) {
+        *direction = turn;
     }
+    false
+}
 
-    if matches!(last_direction.0, Direction::Left | Direction::Right) {
-        *direction = match pressed_direction.y {
-            1.0 => Direction::Up,
-            -1.0 => Direction::Down,
-            _ => direction.clone(),
+/// Resolves the combined pressed directions to a turn, if any.
+///
+/// By default only the axis perpendicular to the last movement is considered, so the snake
+/// can't reverse into itself and e.g. up+left while moving right always turns up. With the
+/// [`TurnPriority::Fixed`] order the vertical keys come first instead, so e.g. up+right while
+/// moving up doesn't turn at all, as up wins and is no turn. Opposite keys on an axis cancel
+/// each other out either way.
+fn resolve_turn(
+    last_direction: &Direction,
+    pressed_direction: IVec2,
+    priority: TurnPriority,
+) -> Option<Direction> {
+    let is_horizontal = matches!(last_direction, Direction::Left | Direction::Right);
+    if priority == TurnPriority::Fixed && pressed_direction.y != 0 && !is_horizontal {
+        return None;
+    }
+    if is_horizontal {
+        match pressed_direction.y {
+            1 => Some(Direction::Up),
+            -1 => Some(Direction::Down),
+            _ => None,
         }
     } else {
-        *direction = match pressed_direction.x {
-            -1.0 => Direction::Left,
-            1.0 => Direction::Right,
-            _ => direction.clone(),
+        match pressed_direction.x {
+            -1 => Some(Direction::Left),
+            1 => Some(Direction::Right),
+            _ => None,
         }
     }
 }
 
+/// Removes the tail after the head moved on, unless the snake is still growing.
+///
+/// Once the snake reached the maximum length, any growth left is dropped, so eating keeps
+/// scoring without making the snake longer.
 fn remove_tail(
     mut commands: Commands,
     query: Query<(Entity, &NextBodyPart), With<Tail>>,
+    parts: Query<(), With<BodyPart>>,
     constants: Res<Constants>,
+    mut growth: ResMut<Growth>,
+    settings: Res<Settings>,
 ) {
+    // The new head is already counted, so keeping the tail makes the snake this long.
+    let is_at_max_length = settings
+        .max_length
+        .is_some_and(|max_length| parts.iter().count() as u32 > max_length);
+    if is_at_max_length {
+        *growth = Growth::default();
+    }
+    if growth.delay > 0 {
+        growth.delay -= 1;
+    } else if growth.segments > 0 {
+        growth.segments -= 1;
+        return;
+    }
     let (tail, next_part) = query.single();
     commands.entity(tail).despawn();
     commands
         .entity(next_part.0.expect("expected tail to have a next_part"))
         .remove::<Body>()
-        .insert((
-            Tail,
-            Sprite::from_image(constants.snake_texture_handles[&SnakePart::Tail].clone()),
-        ));
+        .insert((Tail, constants.part_sprite(SnakePart::Tail)));
+}
+
+/// Cells of the head, body and tail at the start of a run, with the body trailing behind the
+/// head when moving in `direction`.
+fn start_cells(direction: &Direction) -> [Cell; 3] {
+    let step = direction.to_ivec2();
+    [Cell(IVec2::ZERO), Cell(-step), Cell(-2 * step)]
 }
 
 fn spawn_part<Part: Component>(
     commands: &mut Commands,
     part: Part,
-    position: Vec2,
-    image: Handle<Image>,
+    cell: Cell,
+    board: &Board,
+    sprite: Sprite,
+    rotation: Quat,
     next_part: NextBodyPart,
 ) -> Entity {
     commands
@@ -498,60 +1463,151 @@ fn spawn_part<Part: Component>(
             part,
             BodyPart,
             next_part,
-            Sprite::from_image(image),
-            Transform::from_xyz(position.x, position.y, -1.0),
+            cell,
+            sprite,
+            Transform::from_translation(board.cell_to_world(cell).extend(-1.0))
+                .with_rotation(rotation),
         ))
         .id()
 }
 
+/// Spawns a snake on the given cells from tail to head, which must be neighbors. The head faces
+/// away from the neck, with the body bending along the cells.
+fn spawn_snake(commands: &mut Commands, cells: &[Cell], board: &Board, constants: &Constants) {
+    let (head_cell, rest) = cells.split_last().expect("expected a head cell");
+    let head_direction =
+        Direction::between(cells[rest.len() - 1], *head_cell).expect("expected neighbors");
+    let mut next = spawn_part(
+        commands,
+        Head,
+        *head_cell,
+        board,
+        constants.part_sprite(SnakePart::Head),
+        Quat::from_rotation_z(head_direction.to_radians()),
+        NextBodyPart(None),
+    );
+    for index in (1..rest.len()).rev() {
+        let cell = cells[index];
+        let incoming = Direction::between(cells[index - 1], cell).expect("expected neighbors");
+        let outgoing = Direction::between(cell, cells[index + 1]).expect("expected neighbors");
+        let is_clockwise = incoming.is_clockwise(&outgoing);
+        let part = if incoming == outgoing {
+            SnakePart::Body
+        } else if is_clockwise {
+            SnakePart::BodyBent2
+        } else {
+            SnakePart::BodyBent
+        };
+        let sprite = constants.flipped_part_sprite(part, is_clockwise);
+        next = spawn_part(
+            commands,
+            Body,
+            cell,
+            board,
+            sprite,
+            Quat::from_rotation_z(incoming.to_radians()),
+            NextBodyPart(Some(next)),
+        );
+    }
+    let tail_direction = Direction::between(cells[0], cells[1]).expect("expected neighbors");
+    spawn_part(
+        commands,
+        Tail,
+        cells[0],
+        board,
+        constants.part_sprite(SnakePart::Tail),
+        Quat::from_rotation_z(tail_direction.to_radians()),
+        NextBodyPart(Some(next)),
+    );
+}
+
 fn spawn_apple(
     commands: &mut Commands,
-    size: f32,
+    rng: &mut GameRng,
+    board: &Board,
     apple_texture: Handle<Image>,
-    body_part_positions: Vec<Vec2>,
+    occupied: &HashSet<Cell>,
+    settings: &Settings,
 ) {
-    let mut spawn_points = Vec::new();
-    for x in -6..=6 {
-        for y in -6..=6 {
-            spawn_points.push(Vec2::new(x as f32 * size, y as f32 * size));
-        }
-    }
-    for position in body_part_positions {
-        spawn_points.retain(|p| p != &position);
-    }
+    let spawn_cell = settings
+        .apple_placement
+        .strategy()
+        .choose(board, occupied, &mut rng.0)
+        .expect("expected spawn point");
+    debug_assert!(
+        !occupied.contains(&spawn_cell),
+        "apple spawned on an occupied cell at {spawn_cell:?}"
+    );
+    spawn_apple_at(
+        commands,
+        spawn_cell,
+        board,
+        apple_texture,
+        AppleValue(settings.apple_value),
+    );
+}
 
-    spawn_points.shuffle(&mut rand::rng());
+fn spawn_apple_at(
+    commands: &mut Commands,
+    cell: Cell,
+    board: &Board,
+    apple_texture: Handle<Image>,
+    value: AppleValue,
+) {
     commands.spawn((
         Custom,
         Apple,
+        value,
+        AppleAge::default(),
+        cell,
         Sprite::from_image(apple_texture),
-        Transform::from_translation(
-            spawn_points
-                .first()
-                .expect("expected spawn point")
-                .extend(-1.0),
-        ),
+        Transform::from_translation(board.cell_to_world(cell).extend(-1.0)),
     ));
 }
 
+/// Picks a random free cell for a pickup, or `None` if the whole board is occupied.
+///
+/// Pickups other than the regular apple always spawn uniformly, regardless of the chosen
+/// [`PlacementMode`].
+fn choose_apple_spawn_cell(
+    board: &Board,
+    occupied: &HashSet<Cell>,
+    rng: &mut StdRng,
+) -> Option<Cell> {
+    UniformPlacement.choose(board, occupied, rng)
+}
+
+/// Queues the growth right away, so without a delay the tail already stays in place during the
+/// step that eats the apple. A delay only starts if the snake isn't still growing from an
+/// earlier apple.
+///
+/// Extra apples of a frenzy count just like the regular apple.
+#[allow(clippy::type_complexity)]
 fn eat_apple(
-    head_query: Query<&Transform, With<Head>>,
-    apple_query: Query<(Entity, &Transform), With<Apple>>,
+    head_query: Query<&Cell, With<Head>>,
+    apple_query: Query<(Entity, &Cell), Or<(With<Apple>, With<FrenzyApple>)>>,
     mut apple_eaten_event: EventWriter<AppleEatenEvent>,
+    mirror_mode: Res<MirrorMode>,
+    mut growth: ResMut<Growth>,
+    settings: Res<Settings>,
 ) {
-    let head_transform = head_query.single();
-    let (apple, apple_transform) = apple_query.single();
-
-    if head_transform.translation.truncate() == apple_transform.translation.truncate() {
-        apple_eaten_event.send(AppleEatenEvent(apple));
+    let head_cell = head_query.single();
+    for (apple, apple_cell) in apple_query.iter() {
+        if head_cell == apple_cell || (mirror_mode.0 && mirror_cell(*head_cell) == *apple_cell) {
+            if growth.segments == 0 {
+                growth.delay = settings.growth_delay;
+            }
+            growth.segments += settings.growth_per_apple;
+            apple_eaten_event.send(AppleEatenEvent(apple));
+        }
     }
 }
 
 fn play_crunch_sound(mut commands: Commands, apple_crunch: Res<AppleCrunch>) {
-    let handle = apple_crunch
-        .handles
-        .choose(&mut rand::rng())
-        .expect("handles");
+    // Without any sounds to pick from, eating is silent.
+    let Some(handle) = apple_crunch.handles.choose(&mut rand::rng()) else {
+        return;
+    };
 
     commands.spawn((
         Custom,
@@ -560,97 +1616,977 @@ fn play_crunch_sound(mut commands: Commands, apple_crunch: Res<AppleCrunch>) {
     ));
 }
 
+#[allow(clippy::too_many_arguments)]
 fn grow(
     mut commands: Commands,
     mut apple_eaten_event: EventReader<AppleEatenEvent>,
     constants: Res<Constants>,
-    body_parts: Query<&Transform, With<BodyPart>>,
+    mut rng: ResMut<GameRng>,
+    board: Res<Board>,
+    occupancy: Occupancy,
+    apples: Query<(), With<Apple>>,
+    settings: Res<Settings>,
 ) {
+    let mut is_apple_eaten = false;
     for apple in apple_eaten_event.read() {
         commands.entity(apple.0).despawn();
+        is_apple_eaten |= apples.contains(apple.0);
+    }
+    // Eaten frenzy apples aren't replaced.
+    if !is_apple_eaten {
+        return;
     }
-
-    let positions = body_parts
-        .iter()
-        .map(|t| t.translation.truncate())
-        .collect::<Vec<_>>();
 
     spawn_apple(
         &mut commands,
-        constants.size,
+        &mut rng,
+        &board,
         constants.apple_texture_handle.clone(),
-        positions,
+        &occupancy.cells(),
+        &settings,
     );
 }
 
+/// Scores every eaten apple by its value times the combo multiplier, plus the route bonus, all
+/// doubled in a risk run.
+///
+/// Runs before `grow` despawns the eaten apples, so their values can still be read.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 fn update_score(
     mut set: ParamSet<(
-        Query<(&mut Text2d, &mut Score)>,
-        Query<(&mut Text2d, &mut HighScore)>,
+        Query<(&mut Text, &mut Score)>,
+        Query<(&mut Text, &mut HighScore)>,
     )>,
+    mut apple_eaten_event: EventReader<AppleEatenEvent>,
+    apple_values: Query<&AppleValue>,
+    mut combo: ResMut<Combo>,
+    mut route_bonus: ResMut<RouteBonus>,
+    mut high_scores: ResMut<HighScores>,
+    head: Query<&Cell, With<Head>>,
+    board: Res<Board>,
+    mode: Res<GameMode>,
+    risk_mode: Res<RiskMode>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    benchmark: Option<Res<Benchmark>>,
 ) {
     let current_score;
     {
         let mut q_score = set.p0();
         let (mut text, mut score) = q_score.single_mut();
-        score.0 += 1;
+        let mut points = 0;
+        for apple in apple_eaten_event.read() {
+            let value = apple_values.get(apple.0).map_or(1, |value| value.0);
+            points += value * combo.register_apple(time.elapsed(), &settings);
+        }
+        points += route_bonus.register_apple(*head.single(), &board, &settings);
+        score.0 += points * risk_mode.score_multiplier();
         current_score = score.0;
         text.0 = format!("Score: {}", score.0);
     }
 
     let mut q_high_score = set.p1();
     let (mut text, mut high_score) = q_high_score.single_mut();
+    // Apples can be worth more than one point, so catch up in one go.
     if high_score.0 < current_score {
-        high_score.0 += 1;
-        text.0 = format!("Highest: {}", high_score.0);
-        save_high_score(&high_score).expect("could not save high score");
+        high_score.0 = current_score;
+        text.0 = format!("{}: {}", mode.high_score_label(), high_score.0);
+        high_scores.set(*mode, high_score.0);
+        if benchmark.is_none() {
+            save_high_scores(&high_scores).expect("could not save high scores");
+        }
     }
 }
 
+fn pause_gameplay_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_gameplay_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
 fn toggle_pause_game(
     keys: Res<ButtonInput<KeyCode>>,
     state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut query: Query<&mut Visibility, With<PausedOverlay>>,
 ) {
     if keys.just_pressed(KeyCode::Escape) {
-        let mut visibility = query.single_mut();
         match state.get() {
-            GameState::Paused => {
-                next_state.set(GameState::Running);
-                *visibility = Visibility::Hidden;
-            }
-            GameState::Running => {
-                next_state.set(GameState::Paused);
-                *visibility = Visibility::Inherited;
-            }
+            GameState::Paused => next_state.set(GameState::Running),
+            GameState::Running => next_state.set(GameState::Paused),
+            // These screens handle their own way back.
+            GameState::Intro
+            | GameState::Options
+            | GameState::HowToPlay
+            | GameState::Editor
+            | GameState::Console
+            | GameState::GameOver => {}
         }
     }
 }
 
+fn show_paused_overlay(mut query: Query<(&mut PausedOverlay, &mut Visibility)>) {
+    let (mut overlay, mut visibility) = query.single_mut();
+    overlay.dimmed = true;
+    *visibility = Visibility::Inherited;
+}
+
+/// Summary of the run shown below the pause menu.
+fn update_paused_status(
+    mut status: Query<&mut Text2d, With<PausedStatus>>,
+    score: Query<&Score>,
+    parts: Query<(), With<BodyPart>>,
+    movement_timer: Query<&MovementTimer>,
+    mode: Res<GameMode>,
+) {
+    let (Ok(mut status), Ok(score), Ok(movement_timer)) = (
+        status.get_single_mut(),
+        score.get_single(),
+        movement_timer.get_single(),
+    ) else {
+        return;
+    };
+    let text = format!(
+        "Score: {}    Length: {}    Speed: {}ms per cell    Mode: {:?}",
+        score.0,
+        parts.iter().count(),
+        movement_timer.0.duration().as_millis(),
+        *mode
+    );
+    if status.0 != text {
+        status.0 = text;
+    }
+}
+
+fn hide_paused_overlay(mut query: Query<&mut PausedOverlay>) {
+    query.single_mut().dimmed = false;
+}
+
+/// Fades the paused overlay and its texts towards their target, starting from the current alpha
+/// so toggling the pause mid-fade just turns the fade around.
+fn fade_paused_overlay(
+    mut query: Query<(
+        &PausedOverlay,
+        &MeshMaterial2d<ColorMaterial>,
+        &mut Visibility,
+        &Children,
+    )>,
+    mut texts: Query<&mut TextColor>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time<Real>>,
+) {
+    let Ok((overlay, material, mut visibility, children)) = query.get_single_mut() else {
+        return;
+    };
+    let Some(material) = color_materials.get_mut(&material.0) else {
+        return;
+    };
+    let target = if overlay.dimmed { PAUSE_DIM } else { 0.0 };
+    let alpha = material.color.alpha();
+    if alpha == target {
+        return;
+    }
+
+    let step = PAUSE_DIM / PAUSE_FADE_DURATION * time.delta_secs();
+    let alpha = if alpha < target {
+        (alpha + step).min(target)
+    } else {
+        (alpha - step).max(target)
+    };
+    material.color.set_alpha(alpha);
+    for child in children.iter() {
+        if let Ok(mut text_color) = texts.get_mut(*child) {
+            text_color.0.set_alpha(alpha / PAUSE_DIM);
+        }
+    }
+    if alpha == 0.0 {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+/// Ends the run if the head hit something lethal, unless a held shield is used up instead.
+#[allow(clippy::too_many_arguments)]
 fn check_game_over(
-    constants: Res<Constants>,
     mut game_over_event: EventWriter<GameOverEvent>,
-    query: Query<&Transform, With<BodyPart>>,
+    query: Query<&Cell, With<BodyPart>>,
+    head: Query<&Cell, With<Head>>,
+    obstacles: Query<&Cell, With<Obstacle>>,
+    collision_rules: Res<CollisionRules>,
+    spawn_invulnerability: Res<SpawnInvulnerability>,
+    mirror_mode: Res<MirrorMode>,
+    mut shield: ResMut<Shield>,
 ) {
-    let positions = query
-        .iter()
-        .map(|t| {
-            (
-                (t.translation.x / constants.size) as isize,
-                (t.translation.y / constants.size) as isize,
-            )
-        })
-        .collect::<Vec<_>>();
-    let len = positions.len();
-    let unique_positions = HashSet::from_iter(positions);
-    if len != unique_positions.len() {
-        game_over_event.send(GameOverEvent {});
+    if spawn_invulnerability.is_active() {
+        return;
+    }
+    let unique_cells = query.iter().collect::<HashSet<_>>();
+    let head_cell = head.single();
+    // Only the head's own cell counts, the body can still overlap itself where a shielded head
+    // passed through it.
+    let hits_self = collision_rules.self_collision
+        && query.iter().filter(|cell| *cell == head_cell).count() > 1;
+    let hits_obstacle = collision_rules.obstacles && obstacles.iter().any(|cell| cell == head_cell);
+    // Either head running into the other snake is the same as the head meeting a mirrored cell.
+    let hits_mirror = collision_rules.other_snakes
+        && mirror_mode.0
+        && unique_cells
+            .iter()
+            .any(|cell| mirror_cell(**cell) == *head_cell);
+    if hits_self || hits_obstacle || hits_mirror {
+        if shield.0 {
+            shield.0 = false;
+        } else {
+            game_over_event.send(GameOverEvent {});
+        }
     }
 }
 
-fn despawn_all(mut commands: Commands, query: Query<Entity, With<Custom>>) {
+/// Despawns every entity tagged with `T`, along with its children.
+fn despawn_with<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apple_placement::PlacementMode;
+    use crate::hazard_apple::{HazardApple, HazardPenalty};
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    /// The shortest movement interval the options offer.
+    const FASTEST_INTERVAL: u64 = 50;
+
+    /// Runs the movement step of a run without a window, with the snake on `snake` from tail
+    /// to head and an apple on each of `apples`. Every update advances time by `frame`.
+    fn movement_app(settings: Settings, snake: &[Cell], apples: &[Cell], frame: Duration) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame))
+            .add_event::<MovementEvent>()
+            .add_event::<AppleEatenEvent>()
+            .add_event::<GameOverEvent>()
+            .add_event::<WrapEvent>()
+            .insert_resource(Board::from_settings(&settings, 1.0))
+            .insert_resource(Constants {
+                apple_texture_handle: default(),
+                hazard_apple_texture_handle: default(),
+                snake_texture_handles: HashMap::from_iter(
+                    [
+                        SnakePart::Head,
+                        SnakePart::Body,
+                        SnakePart::BodyBent,
+                        SnakePart::BodyBent2,
+                        SnakePart::Tail,
+                    ]
+                    .map(|part| (part, default())),
+                ),
+                snake_anchors: snake_anchors(),
+            })
+            .insert_resource(GameRng(StdRng::seed_from_u64(1)))
+            .insert_resource(CollisionRules::from_settings(&settings))
+            .insert_resource(SpawnInvulnerability::new(&settings))
+            .insert_resource(MirrorMode(false))
+            .insert_resource(WallHits::default())
+            .insert_resource(Stalled::default())
+            .insert_resource(Growth::default())
+            .insert_resource(Shield::default())
+            .insert_resource(settings)
+            .init_asset::<Font>()
+            .init_resource::<GameFont>()
+            .add_systems(
+                Update,
+                (
+                    trigger_movement,
+                    tick_spawn_invulnerability,
+                    (
+                        move_head,
+                        (
+                            adjust_head_direction,
+                            eat_apple,
+                            eat_hazard_apple,
+                            remove_tail,
+                            adjust_tail_direction,
+                            check_game_over,
+                        )
+                            .chain()
+                            .run_if(has_moved),
+                    )
+                        .chain()
+                        .run_if(on_event::<MovementEvent>),
+                    grow.after(check_game_over)
+                        .run_if(on_event::<AppleEatenEvent>),
+                )
+                    .chain(),
+            );
+
+        let world = app.world_mut();
+        let direction = Direction::between(snake[snake.len() - 2], snake[snake.len() - 1])
+            .expect("expected neighbors");
+        world.spawn((direction.clone(), LastDirection(direction)));
+        world.spawn((Score(0), Text::default()));
+        let interval = Duration::from_millis(world.resource::<Settings>().movement_interval);
+        world.spawn(MovementTimer(Timer::new(interval, TimerMode::Repeating)));
+        let board = *world.resource::<Board>();
+        for apple in apples {
+            world.spawn((Apple, AppleValue(1), *apple));
+        }
+        let constants = world.remove_resource::<Constants>().unwrap();
+        spawn_snake(&mut world.commands(), snake, &board, &constants);
+        world.insert_resource(constants);
+        world.flush();
+        app
+    }
+
+    fn head_cell(app: &mut App) -> Cell {
+        *app.world_mut()
+            .query_filtered::<&Cell, With<Head>>()
+            .single(app.world())
+    }
+
+    fn snake_cells(app: &mut App) -> Vec<Cell> {
+        app.world_mut()
+            .query_filtered::<&Cell, With<BodyPart>>()
+            .iter(app.world())
+            .copied()
+            .collect()
+    }
+
+    fn is_game_over(app: &App) -> bool {
+        !app.world().resource::<Events<GameOverEvent>>().is_empty()
+    }
+
+    #[test]
+    fn same_seed_places_the_same_apples() {
+        let board = Board::new(15, 1.0);
+        let occupied = HashSet::from_iter(start_cells(&Direction::Right));
+        let mut first = GameRng::from_seed(Seed(42));
+        let mut second = GameRng::from_seed(Seed(42));
+
+        for mode in PlacementMode::ALL {
+            let apples = |rng: &mut GameRng| {
+                (0..100)
+                    .map(|_| mode.strategy().choose(&board, &occupied, &mut rng.0))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(apples(&mut first), apples(&mut second), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn starts_moving_away_from_the_body_in_every_direction() {
+        for direction in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            let [head, body, tail] = start_cells(&direction);
+            assert_eq!(head, Cell::new(0, 0));
+            assert_eq!(Direction::between(tail, body), Some(direction.clone()));
+            assert_eq!(Direction::between(body, head), Some(direction.clone()));
+
+            let settings = Settings {
+                spawn_invulnerability: 0.0,
+                ..default()
+            };
+            let snake = [tail, body, head];
+            let mut app = movement_app(settings, &snake, &[], Duration::from_millis(100));
+            app.update();
+            app.update();
+            assert_eq!(
+                head_cell(&mut app),
+                Cell(direction.to_ivec2()),
+                "{direction:?}"
+            );
+            assert!(!is_game_over(&app), "{direction:?}");
+        }
+    }
+
+    /// Runs the scoring of eaten apples, starting with the given high score.
+    fn score_app(high_score: u32) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<AppleEatenEvent>()
+            .insert_resource(Board::new(15, 1.0))
+            .insert_resource(GameMode::Endless)
+            .insert_resource(RiskMode(false))
+            .insert_resource(Settings::default())
+            .init_resource::<Combo>()
+            .init_resource::<RouteBonus>()
+            .init_resource::<HighScores>()
+            .add_systems(Update, update_score.run_if(on_event::<AppleEatenEvent>));
+        let world = app.world_mut();
+        world.spawn((Score(0), Text::default()));
+        world.spawn((HighScore(high_score), Text::default()));
+        world.spawn((Head, Cell::new(0, 0)));
+        app
+    }
+
+    /// Eats apples worth the given points in one step.
+    fn eat_apples(app: &mut App, values: &[u32]) {
+        for value in values {
+            let apple = app.world_mut().spawn(AppleValue(*value)).id();
+            app.world_mut().send_event(AppleEatenEvent(apple));
+        }
+        app.update();
+    }
+
+    fn score(app: &mut App) -> u32 {
+        app.world_mut().query::<&Score>().single(app.world()).0
+    }
+
+    #[test]
+    fn apples_score_their_value() {
+        let mut app = score_app(0);
+        eat_apples(&mut app, &[1]);
+        assert_eq!(score(&mut app), 1);
+        eat_apples(&mut app, &[3]);
+        assert_eq!(score(&mut app), 4);
+        eat_apples(&mut app, &[2, 5]);
+        assert_eq!(score(&mut app), 11);
+    }
+
+    #[test]
+    fn high_score_catches_up_with_multi_point_apples() {
+        let mut app = score_app(2);
+        eat_apples(&mut app, &[5]);
+
+        let mut high_score = app.world_mut().query::<&HighScore>();
+        assert_eq!(high_score.single(app.world()).0, 5);
+        assert_eq!(
+            app.world().resource::<HighScores>().get(GameMode::Endless),
+            5
+        );
+    }
+
+    const UP_LEFT: IVec2 = IVec2::new(-1, 1);
+
+    #[test]
+    fn simultaneous_presses_turn_along_the_perpendicular_axis() {
+        let turning = TurnPriority::Turning;
+        assert_eq!(
+            resolve_turn(&Direction::Right, UP_LEFT, turning),
+            Some(Direction::Up)
+        );
+        assert_eq!(
+            resolve_turn(&Direction::Left, UP_LEFT, turning),
+            Some(Direction::Up)
+        );
+        assert_eq!(
+            resolve_turn(&Direction::Up, UP_LEFT, turning),
+            Some(Direction::Left)
+        );
+        assert_eq!(
+            resolve_turn(&Direction::Down, UP_LEFT, turning),
+            Some(Direction::Left)
+        );
+    }
+
+    #[test]
+    fn simultaneous_presses_follow_the_turn_priority() {
+        let up_right = IVec2::new(1, 1);
+        for (last_direction, turning, fixed) in [
+            (Direction::Up, Some(Direction::Right), None),
+            (Direction::Down, Some(Direction::Right), None),
+            (Direction::Left, Some(Direction::Up), Some(Direction::Up)),
+            (Direction::Right, Some(Direction::Up), Some(Direction::Up)),
+        ] {
+            assert_eq!(
+                resolve_turn(&last_direction, up_right, TurnPriority::Turning),
+                turning,
+                "{last_direction:?}"
+            );
+            assert_eq!(
+                resolve_turn(&last_direction, up_right, TurnPriority::Fixed),
+                fixed,
+                "{last_direction:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn opposite_presses_cancel_out() {
+        let up_down_left = IVec2::new(-1, 0);
+        assert_eq!(
+            resolve_turn(&Direction::Right, up_down_left, TurnPriority::Turning),
+            None
+        );
+        assert_eq!(
+            resolve_turn(&Direction::Up, IVec2::ZERO, TurnPriority::Turning),
+            None
+        );
+    }
+
+    #[test]
+    fn steering_ignores_reversals() {
+        let mut direction = Direction::Right;
+        let last_direction = LastDirection(Direction::Right);
+        let is_reversal = steer(
+            &mut direction,
+            &last_direction,
+            IVec2::NEG_X,
+            false,
+            TurnPriority::Turning,
+        );
+        assert!(is_reversal);
+        assert_eq!(direction, Direction::Right);
+    }
+
+    #[test]
+    fn strict_steering_keeps_the_first_turn_of_a_step() {
+        let last_direction = LastDirection(Direction::Right);
+        let mut direction = Direction::Right;
+        steer(
+            &mut direction,
+            &last_direction,
+            IVec2::Y,
+            true,
+            TurnPriority::Turning,
+        );
+        steer(
+            &mut direction,
+            &last_direction,
+            IVec2::NEG_Y,
+            true,
+            TurnPriority::Turning,
+        );
+        assert_eq!(direction, Direction::Up);
+
+        let mut direction = Direction::Right;
+        steer(
+            &mut direction,
+            &last_direction,
+            IVec2::Y,
+            false,
+            TurnPriority::Turning,
+        );
+        steer(
+            &mut direction,
+            &last_direction,
+            IVec2::NEG_Y,
+            false,
+            TurnPriority::Turning,
+        );
+        assert_eq!(direction, Direction::Down);
+    }
+
+    #[test]
+    fn crashing_into_a_wall_keeps_the_snake_as_it_was() {
+        let settings = Settings {
+            solid_walls: true,
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let edge = settings.board_size / 2;
+        let snake = [
+            Cell::new(edge - 2, 0),
+            Cell::new(edge - 1, 0),
+            Cell::new(edge, 0),
+        ];
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &[], frame);
+
+        assert_eq!(lengths(&mut app, 1), [3]);
+        assert!(is_game_over(&app));
+        assert_eq!(head_cell(&mut app), Cell::new(edge, 0));
+    }
+
+    #[test]
+    fn zen_runs_bounce_out_of_a_corner() {
+        let settings = Settings {
+            zen_mode: true,
+            solid_walls: true,
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let edge = settings.board_size / 2;
+        // Heading into the corner with the tail on the only cell to turn to.
+        let snake = [
+            Cell::new(edge, edge - 1),
+            Cell::new(edge - 1, edge - 1),
+            Cell::new(edge - 1, edge),
+            Cell::new(edge, edge),
+        ];
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &[], frame);
+
+        assert_eq!(lengths(&mut app, 3), [4, 4, 4]);
+        assert!(!is_game_over(&app));
+        assert_eq!(head_cell(&mut app), Cell::new(edge, edge - 3));
+    }
+
+    /// Lengths of the snake after each of `steps` steps.
+    fn lengths(app: &mut App, steps: usize) -> Vec<usize> {
+        // The first update only starts the movement timer.
+        app.update();
+        (0..steps)
+            .map(|_| {
+                app.update();
+                snake_cells(app).len()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn grows_by_the_growth_per_apple_over_the_next_steps() {
+        let settings = Settings {
+            growth_per_apple: 3,
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &[Cell::new(1, 0)], frame);
+
+        assert_eq!(lengths(&mut app, 5), [4, 5, 6, 6, 6]);
+    }
+
+    #[test]
+    fn stops_growing_at_the_maximum_length() {
+        let settings = Settings {
+            max_length: Some(5),
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+        let apples = [1, 2, 3, 4].map(|x| Cell::new(x, 0));
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &apples, frame);
+        let mut eaten = app
+            .world()
+            .resource::<Events<AppleEatenEvent>>()
+            .get_cursor();
+
+        // The first update only starts the movement timer.
+        app.update();
+        for expected in [4, 5, 5, 5] {
+            app.update();
+            assert_eq!(snake_cells(&mut app).len(), expected);
+            // The apples past the cap still count.
+            let events = app.world().resource::<Events<AppleEatenEvent>>();
+            assert_eq!(eaten.read(events).count(), 1);
+        }
+    }
+
+    #[test]
+    fn growth_delay_holds_back_the_growth() {
+        let settings = Settings {
+            growth_per_apple: 2,
+            growth_delay: 2,
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &[Cell::new(1, 0)], frame);
+
+        assert_eq!(lengths(&mut app, 6), [3, 3, 4, 5, 5, 5]);
+    }
+
+    #[test]
+    fn strict_turns_apply_only_the_first_press_of_a_step() {
+        for (strict_turns, expected) in [(true, Cell::new(0, 1)), (false, Cell::new(0, -1))] {
+            let settings = Settings {
+                strict_turns,
+                spawn_invulnerability: 0.0,
+                ..default()
+            };
+            let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+            let mut app = movement_app(settings, &snake, &[], Duration::from_millis(30));
+            app.add_event::<BlockedTurnEvent>()
+                .init_resource::<ButtonInput<KeyCode>>()
+                .add_systems(Update, change_direction.before(trigger_movement));
+
+            // Both presses land before the first step.
+            for key in [KeyCode::ArrowUp, KeyCode::ArrowDown] {
+                let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+                keys.reset_all();
+                keys.press(key);
+                app.update();
+            }
+            app.world_mut()
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .reset_all();
+            while head_cell(&mut app) == Cell::new(0, 0) {
+                app.update();
+            }
+            assert_eq!(head_cell(&mut app), expected, "strict: {strict_turns}");
+        }
+    }
+
+    #[test]
+    fn collisions_are_ignored_right_after_spawning() {
+        let settings = Settings {
+            spawn_invulnerability: 0.35,
+            ..default()
+        };
+        let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &[], frame);
+        for x in 1..=6 {
+            app.world_mut().spawn((Obstacle, Cell::new(x, 0)));
+        }
+
+        // The first update only starts the movement timer.
+        app.update();
+        for step in 1..=3 {
+            app.update();
+            assert_eq!(head_cell(&mut app), Cell::new(step, 0));
+            assert!(!is_game_over(&app), "ended at step {step}");
+        }
+        app.update();
+        assert!(is_game_over(&app));
+    }
+
+    #[test]
+    fn blocked_reversals_flash_the_head() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_event::<BlockedTurnEvent>()
+            .init_resource::<BlockedTurnFlash>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(Settings {
+                blocked_turn_feedback: true,
+                ..default()
+            })
+            .add_systems(Update, (change_direction, flash_blocked_turn).chain());
+        app.world_mut()
+            .spawn((Direction::Right, LastDirection(Direction::Right)));
+        let head = app.world_mut().spawn((Head, Sprite::default())).id();
+        let mut blocked = app
+            .world()
+            .resource::<Events<BlockedTurnEvent>>()
+            .get_cursor();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ArrowLeft);
+        app.update();
+
+        let events = app.world().resource::<Events<BlockedTurnEvent>>();
+        assert_eq!(blocked.read(events).count(), 1);
+        let mut direction = app.world_mut().query::<&Direction>();
+        assert_eq!(*direction.single(app.world()), Direction::Right);
+        assert_ne!(app.world().get::<Sprite>(head).unwrap().color, Color::WHITE);
+    }
+
+    #[test]
+    fn shield_negates_one_collision() {
+        let settings = Settings {
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &[], frame);
+        app.insert_resource(Shield(true));
+        for x in [1, 2] {
+            app.world_mut().spawn((Obstacle, Cell::new(x, 0)));
+        }
+
+        // The first update only starts the movement timer.
+        app.update();
+        app.update();
+        assert_eq!(head_cell(&mut app), Cell::new(1, 0));
+        assert!(!is_game_over(&app));
+        assert!(
+            !app.world().resource::<Shield>().0,
+            "the shield wasn't used up"
+        );
+
+        app.update();
+        assert!(is_game_over(&app));
+    }
+
+    #[test]
+    fn hazard_apples_apply_the_penalty() {
+        for penalty in [HazardPenalty::EndRun, HazardPenalty::LosePoints] {
+            let settings = Settings {
+                hazard_penalty: penalty,
+                spawn_invulnerability: 0.0,
+                ..default()
+            };
+            let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+            let frame = Duration::from_millis(settings.movement_interval);
+            let mut app = movement_app(settings, &snake, &[], frame);
+            let hazard_apple = app.world_mut().spawn((HazardApple, Cell::new(1, 0))).id();
+            let mut score = app.world_mut().query::<&mut Score>();
+            score.single_mut(app.world_mut()).0 = 12;
+
+            // The first update only starts the movement timer.
+            app.update();
+            app.update();
+
+            assert!(app.world().get_entity(hazard_apple).is_err(), "{penalty:?}");
+            assert_eq!(snake_cells(&mut app).len(), 3, "{penalty:?}");
+            let score = score.single(app.world()).0;
+            match penalty {
+                HazardPenalty::EndRun => {
+                    assert!(is_game_over(&app));
+                    assert_eq!(score, 12);
+                }
+                HazardPenalty::LosePoints => {
+                    assert!(!is_game_over(&app));
+                    assert_eq!(score, 7);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn head_artwork_stays_on_the_cell_in_every_direction() {
+        let settings = Settings {
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+        let frame = Duration::from_millis(settings.movement_interval);
+        let mut app = movement_app(settings, &snake, &[], frame);
+        let board = *app.world().resource::<Board>();
+        // The head's artwork spans the rows 2 to 45 of the 50 rows of its image, counted from
+        // the top, and all of its columns.
+        let artwork_center = Vec2::new(0.0, (25.0 - 24.0) / 50.0);
+
+        // The first update only starts the movement timer.
+        app.update();
+        for direction in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ] {
+            let mut pending = app.world_mut().query::<&mut Direction>();
+            *pending.single_mut(app.world_mut()) = direction.clone();
+            app.update();
+
+            let mut head = app
+                .world_mut()
+                .query_filtered::<(&Cell, &Sprite, &Transform), With<Head>>();
+            let (cell, sprite, transform) = head.single(app.world());
+            assert_eq!(
+                sprite.anchor,
+                Anchor::Custom(artwork_center),
+                "{direction:?}"
+            );
+            let expected = Quat::from_rotation_z(direction.to_radians());
+            assert!(
+                transform.rotation.abs_diff_eq(expected, 1e-5),
+                "{direction:?}"
+            );
+            let offset = (artwork_center - sprite.anchor.as_vec()) * board.cell_size;
+            let center = transform.transform_point(offset.extend(0.0)).truncate();
+            assert!(
+                center.abs_diff_eq(board.cell_to_world(*cell), 1e-5),
+                "{direction:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn flipped_sprites_keep_the_anchor_on_the_artwork() {
+        let constants = Constants {
+            apple_texture_handle: default(),
+            hazard_apple_texture_handle: default(),
+            snake_texture_handles: HashMap::from([(SnakePart::Head, default())]),
+            snake_anchors: snake_anchors(),
+        };
+        let sprite = constants.flipped_part_sprite(SnakePart::Head, true);
+        assert!(sprite.flip_y);
+        assert_eq!(sprite.anchor, Anchor::Custom(Vec2::new(0.0, -1.0 / 50.0)));
+        let sprite = constants.flipped_part_sprite(SnakePart::Head, false);
+        assert_eq!(sprite.anchor, Anchor::Custom(Vec2::new(0.0, 1.0 / 50.0)));
+    }
+
+    #[test]
+    fn nudged_positions_still_collide_by_cell() {
+        let settings = Settings {
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let snake = [Cell::new(-1, -1), Cell::new(-1, 0), Cell::new(0, 0)];
+        let mut app = movement_app(settings, &snake, &[], Duration::from_millis(100));
+        // Drawn a tiny bit off their cells, like during an animation.
+        let board = *app.world().resource::<Board>();
+        let nudge = Vec3::splat(f32::EPSILON * 8.0);
+        let apple = app
+            .world_mut()
+            .spawn((
+                Apple,
+                AppleValue(1),
+                Cell::new(1, 0),
+                Transform::from_translation(
+                    board.cell_to_world(Cell::new(1, 0)).extend(0.0) + nudge,
+                ),
+            ))
+            .id();
+        let mut parts = app.world_mut().query::<(&mut Transform, &BodyPart)>();
+        for (mut transform, _) in parts.iter_mut(app.world_mut()) {
+            transform.translation -= nudge;
+            transform.rotation = Quat::IDENTITY;
+        }
+
+        // The first update only starts the movement timer.
+        app.update();
+        app.update();
+
+        assert_eq!(head_cell(&mut app), Cell::new(1, 0));
+        assert!(
+            app.world().get_entity(apple).is_err(),
+            "the apple wasn't eaten"
+        );
+        // The tail stays for the growth and is turned to point along the body towards the head.
+        let mut tail = app
+            .world_mut()
+            .query_filtered::<(&Cell, &Transform), With<Tail>>();
+        let (tail_cell, transform) = tail.single(app.world());
+        assert_eq!(*tail_cell, Cell::new(-1, -1));
+        let expected = Quat::from_rotation_z(Direction::Up.to_radians());
+        assert!(transform.rotation.abs_diff_eq(expected, 1e-5));
+    }
+
+    #[test]
+    fn moves_one_cell_per_step_at_the_fastest_speed() {
+        let settings = Settings {
+            movement_interval: FASTEST_INTERVAL,
+            spawn_invulnerability: 0.0,
+            ..default()
+        };
+        let snake = [Cell::new(-2, 0), Cell::new(-1, 0), Cell::new(0, 0)];
+        let apples = [Cell::new(1, 0), Cell::new(2, 0), Cell::new(3, 0)];
+        // Frames much longer than a step, so the movement timer finishes several times a frame.
+        let mut app = movement_app(settings, &snake, &apples, Duration::from_millis(200));
+        let board = *app.world().resource::<Board>();
+
+        let mut previous = head_cell(&mut app);
+        let mut steps = 0;
+        for _ in 0..40 {
+            app.update();
+            let head = head_cell(&mut app);
+            if head != previous {
+                assert_eq!(
+                    head,
+                    board.wrap(Cell(previous.0 + IVec2::X)),
+                    "skipped a cell"
+                );
+                steps += 1;
+            }
+            let cells = snake_cells(&mut app);
+            let unique = cells.iter().collect::<HashSet<_>>();
+            assert_eq!(unique.len(), cells.len(), "passed through the body");
+            assert!(!is_game_over(&app));
+            previous = head;
+        }
+        assert!(steps >= 39, "expected a step every frame, got {steps}");
+
+        // The apples in a row were all eaten, one per step.
+        let mut apples = app.world_mut().query_filtered::<&Cell, With<Apple>>();
+        for cell in apples.iter(app.world()) {
+            assert!(!(1..=3).contains(&cell.x) || cell.y != 0);
+        }
+        assert!(snake_cells(&mut app).len() >= 6);
+    }
+}
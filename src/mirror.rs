@@ -0,0 +1,57 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::{BodyPart, Custom};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+/// How far the snake starts from the center line in mirror mode, so it doesn't start inside its
+/// own mirror image.
+pub const MIRROR_START_OFFSET: IVec2 = IVec2::new(0, 2);
+
+/// Whether the current run has a second snake mirroring the first across the horizontal center
+/// line of the board.
+///
+/// The mirror image moves up when the snake moves down and vice versa. Reflection keeps opposite
+/// directions opposite, so the mirrored snake follows the same no-reversal rule as the real one
+/// and needs no steering of its own.
+#[derive(Resource)]
+pub struct MirrorMode(pub bool);
+
+#[derive(Component)]
+pub struct MirrorPart;
+
+pub fn mirror_cell(cell: Cell) -> Cell {
+    Cell::new(cell.x, -cell.y)
+}
+
+pub fn update_mirror_snake(
+    mut commands: Commands,
+    mirror_mode: Res<MirrorMode>,
+    mirror_parts: Query<Entity, With<MirrorPart>>,
+    parts: Query<(&Cell, &Transform, &Sprite), With<BodyPart>>,
+    board: Res<Board>,
+) {
+    for entity in mirror_parts.iter() {
+        commands.entity(entity).despawn();
+    }
+    if !mirror_mode.0 {
+        return;
+    }
+
+    for (cell, transform, sprite) in parts.iter() {
+        let (angle, _, _) = transform.rotation.to_euler(EulerRot::ZYX);
+        commands.spawn((
+            Custom,
+            MirrorPart,
+            Sprite {
+                flip_y: !sprite.flip_y,
+                // Flipped along, so the anchor stays on the artwork.
+                anchor: Anchor::Custom(sprite.anchor.as_vec() * Vec2::new(1.0, -1.0)),
+                color: Color::srgb(0.7, 0.8, 1.0),
+                ..sprite.clone()
+            },
+            Transform::from_translation(board.cell_to_world(mirror_cell(*cell)).extend(-1.0))
+                .with_rotation(Quat::from_rotation_z(-angle)),
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::camera::hud_layer;
+use crate::fonts::CUSTOM_FONT_DIRECTORY;
+use crate::textures::CUSTOM_TEXTURE_DIRECTORY;
+use bevy::asset::AssetLoadFailedEvent;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::PrimaryWindow;
+
+/// Paths of assets that failed to load, which otherwise show up as invisible sprites, silence
+/// or missing text.
+#[derive(Resource, Default)]
+pub struct MissingAssets(Vec<String>);
+
+/// Lists the missing assets at the bottom of the screen. It isn't tied to a run, as restarting
+/// doesn't bring the assets back.
+#[derive(Component)]
+pub struct MissingAssetsText;
+
+pub fn spawn_missing_assets_text(
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+) {
+    let resolution = &window.single().resolution;
+    commands.spawn((
+        MissingAssetsText,
+        hud_layer(),
+        Text2d::default(),
+        TextColor(Color::srgb(1.0, 0.3, 0.2)),
+        // Bevy's built-in font, in case the game's font is one of the missing assets.
+        TextFont {
+            font_size: 20.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(JustifyText::Center),
+        Anchor::BottomCenter,
+        Transform::from_xyz(0.0, resolution.height() / -2.0 + 10.0, 20.0),
+        Visibility::Hidden,
+    ));
+}
+
+pub fn record_missing_assets<A: Asset>(
+    mut events: EventReader<AssetLoadFailedEvent<A>>,
+    mut missing: ResMut<MissingAssets>,
+) {
+    for event in events.read() {
+        let path = event.path.path();
+        // Broken custom textures and fonts fall back to the built-in ones.
+        if path.starts_with(CUSTOM_TEXTURE_DIRECTORY) || path.starts_with(CUSTOM_FONT_DIRECTORY) {
+            continue;
+        }
+        error!("could not load asset {}: {}", event.path, event.error);
+        let path = path.display().to_string();
+        if !missing.0.contains(&path) {
+            missing.0.push(path);
+        }
+    }
+}
+
+pub fn update_missing_assets_text(
+    missing: Res<MissingAssets>,
+    mut query: Query<(&mut Text2d, &mut Visibility), With<MissingAssetsText>>,
+) {
+    let (mut text, mut visibility) = query.single_mut();
+    text.0 = format!(
+        "Missing assets: {}\nMake sure the assets folder is complete.",
+        missing.0.join(", ")
+    );
+    *visibility = if missing.0.is_empty() {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+}
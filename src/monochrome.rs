@@ -0,0 +1,84 @@
+use crate::board::{Board, BoardGeometry, PlayableArea};
+use crate::frenzy::FrenzyApple;
+use crate::rounded_body::RoundedBody;
+use crate::{Apple, BodyPart};
+use bevy::prelude::*;
+
+/// The two colors of the classic handheld look, a pale green screen with dark pixels.
+const BACKGROUND: Color = Color::srgb(0.78, 0.86, 0.6);
+const FOREGROUND: Color = Color::srgb(0.2, 0.25, 0.15);
+
+/// Snake parts are drawn as plain squares a bit smaller than their cell, so the segments stay
+/// apart, and apples as smaller squares, so they can't be mistaken for the snake without color.
+const PART_SIZE: f32 = 0.85;
+const APPLE_SIZE: f32 = 0.5;
+
+/// Whether the current run is drawn in two colors only, fixed when the run starts since the
+/// original textures and colors aren't kept around to switch back.
+#[derive(Resource)]
+pub struct Monochrome(pub bool);
+
+pub fn is_monochrome(monochrome: Res<Monochrome>) -> bool {
+    monochrome.0
+}
+
+/// Draws every sprite in the foreground color, keeping its transparency, and replaces the
+/// textures of the snake and the apple with squares.
+///
+/// Runs after all other systems, as the snake gets new sprites while it moves.
+#[allow(clippy::type_complexity)]
+pub fn apply_monochrome_sprites(
+    board: Res<Board>,
+    mut sprites: Query<(&mut Sprite, Has<BodyPart>, Has<Apple>, Has<FrenzyApple>), Changed<Sprite>>,
+) {
+    for (mut sprite, is_part, is_apple, is_frenzy_apple) in sprites.iter_mut() {
+        let color = FOREGROUND.with_alpha(sprite.color.alpha());
+        if sprite.color != color {
+            sprite.color = color;
+        }
+        let size = if is_part {
+            PART_SIZE
+        } else if is_apple || is_frenzy_apple {
+            APPLE_SIZE
+        } else {
+            continue;
+        };
+        let size = Some(Vec2::splat(board.cell_size * size));
+        if sprite.custom_size != size || sprite.image != Handle::default() {
+            sprite.image = Handle::default();
+            sprite.custom_size = size;
+        }
+    }
+}
+
+/// Draws the playable area in the background color and its walls and the rounded body in the
+/// foreground color.
+#[allow(clippy::type_complexity)]
+pub fn apply_monochrome_materials(
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<
+        (&MeshMaterial2d<ColorMaterial>, Has<PlayableArea>),
+        (
+            Added<MeshMaterial2d<ColorMaterial>>,
+            Or<(With<BoardGeometry>, With<RoundedBody>)>,
+        ),
+    >,
+) {
+    for (material, is_playable_area) in query.iter() {
+        let Some(material) = color_materials.get_mut(&material.0) else {
+            continue;
+        };
+        let color = if is_playable_area {
+            BACKGROUND
+        } else {
+            FOREGROUND
+        };
+        material.color = color.with_alpha(material.color.alpha());
+    }
+}
+
+pub fn apply_monochrome_clear_color(mut clear_color: ResMut<ClearColor>) {
+    if clear_color.0 != BACKGROUND {
+        clear_color.0 = BACKGROUND;
+    }
+}
@@ -0,0 +1,168 @@
+use crate::camera::hud_layer;
+use crate::settings::Settings;
+use crate::{AppleEatenEvent, Custom, GameOverEvent};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::WindowResolution;
+
+/// Apples eaten this run toward the number that wins it, `target` is `None` if the run has no
+/// objective.
+///
+/// Winning ends the run just like losing, the game over screen tells them apart.
+#[derive(Resource)]
+pub struct Objective {
+    eaten: u32,
+    target: Option<u32>,
+}
+
+impl Objective {
+    pub fn new(settings: &Settings) -> Self {
+        Self {
+            eaten: 0,
+            // Zen runs are endless.
+            target: settings.apple_target.filter(|_| !settings.zen_mode),
+        }
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.target.is_some_and(|target| self.eaten >= target)
+    }
+
+    fn progress(&self) -> Option<String> {
+        self.target
+            .map(|target| format!("Apples: {}/{target}", self.eaten))
+    }
+}
+
+#[derive(Component)]
+pub struct ObjectiveText;
+
+pub fn spawn_objective_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    resolution: &WindowResolution,
+    objective: &Objective,
+) {
+    let Some(progress) = objective.progress() else {
+        return;
+    };
+    commands.spawn((
+        Custom,
+        ObjectiveText,
+        hud_layer(),
+        Text2d::new(progress),
+        TextColor(Color::srgb(0.5, 1.0, 1.0)),
+        TextFont {
+            font,
+            font_size: 30.0,
+            ..default()
+        },
+        Anchor::TopRight,
+        // Below the speedometer.
+        Transform::from_translation(Vec3::new(
+            resolution.width() / 2.0 - 20.0,
+            resolution.height() / 2.0 - 30.0,
+            0.0,
+        )),
+    ));
+}
+
+/// Counts every apple eaten this step, a step can eat several, like with extra apples or when
+/// both snakes of mirror mode eat at once.
+pub fn advance_objective(
+    mut objective: ResMut<Objective>,
+    mut apple_eaten_event: EventReader<AppleEatenEvent>,
+    mut game_over_event: EventWriter<GameOverEvent>,
+) {
+    let eaten = apple_eaten_event.read().count() as u32;
+    if objective.target.is_none() {
+        return;
+    }
+    objective.eaten += eaten;
+    if objective.is_won() {
+        game_over_event.send(GameOverEvent);
+    }
+}
+
+pub fn update_objective_text(
+    objective: Res<Objective>,
+    mut query: Query<&mut Text2d, With<ObjectiveText>>,
+) {
+    if let (Some(progress), Ok(mut text)) = (objective.progress(), query.get_single_mut()) {
+        text.0 = progress;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn objective_app(settings: &Settings) -> App {
+        let mut app = App::new();
+        app.add_event::<AppleEatenEvent>()
+            .add_event::<GameOverEvent>()
+            .insert_resource(Objective::new(settings))
+            .add_systems(Update, advance_objective);
+        app
+    }
+
+    /// Eats `count` apples within a single step.
+    fn eat_apples(app: &mut App, count: usize) {
+        for _ in 0..count {
+            app.world_mut()
+                .send_event(AppleEatenEvent(Entity::PLACEHOLDER));
+        }
+        app.update();
+    }
+
+    #[test]
+    fn reaching_the_target_wins_the_run() {
+        let settings = Settings {
+            apple_target: Some(3),
+            ..default()
+        };
+        let mut app = objective_app(&settings);
+        let mut game_over = app.world().resource::<Events<GameOverEvent>>().get_cursor();
+
+        for eaten in 1..=3 {
+            eat_apples(&mut app, 1);
+            let events = app.world().resource::<Events<GameOverEvent>>();
+            let is_won = app.world().resource::<Objective>().is_won();
+            assert_eq!(game_over.read(events).count(), usize::from(eaten == 3));
+            assert_eq!(is_won, eaten == 3);
+        }
+        assert_eq!(
+            app.world().resource::<Objective>().progress().as_deref(),
+            Some("Apples: 3/3")
+        );
+    }
+
+    #[test]
+    fn counts_every_apple_eaten_in_a_step() {
+        let settings = Settings {
+            apple_target: Some(3),
+            ..default()
+        };
+        let mut app = objective_app(&settings);
+
+        eat_apples(&mut app, 2);
+        assert_eq!(
+            app.world().resource::<Objective>().progress().as_deref(),
+            Some("Apples: 2/3")
+        );
+        eat_apples(&mut app, 2);
+        assert!(app.world().resource::<Objective>().is_won());
+    }
+
+    #[test]
+    fn zen_runs_have_no_objective() {
+        let settings = Settings {
+            apple_target: Some(3),
+            zen_mode: true,
+            ..default()
+        };
+        let objective = Objective::new(&settings);
+        assert_eq!(objective.progress(), None);
+        assert!(!objective.is_won());
+    }
+}
@@ -0,0 +1,800 @@
+use crate::camera::{MAX_ZOOM, MIN_ZOOM, ZOOM_STEP};
+use crate::fonts::{GameFont, list_custom_fonts};
+use crate::key_bindings::{Action, KeyBindings, key_name};
+use crate::scenario::list_scenarios;
+use crate::settings::{AntiAliasing, BodyStyle, FrameSync, Settings, save_settings};
+use crate::{Direction, GameState};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+const FONT_SIZE: f32 = 25.0;
+const LINE_HEIGHT: f32 = FONT_SIZE * 1.2;
+
+struct OptionEntry {
+    label: &'static str,
+    value: fn(&Settings) -> String,
+    /// Steps the setting forwards (`1`) or backwards (`-1`).
+    change: fn(&mut Settings, i32),
+}
+
+const OPTIONS: &[OptionEntry] = &[
+    OptionEntry {
+        label: "Body style",
+        value: |settings| format!("{:?}", settings.body_style),
+        change: |settings, _| {
+            settings.body_style = match settings.body_style {
+                BodyStyle::Sprites => BodyStyle::Rounded,
+                BodyStyle::Rounded => BodyStyle::Sprites,
+            }
+        },
+    },
+    OptionEntry {
+        label: "Background",
+        value: |settings| format!("{:?}", settings.background),
+        change: |settings, step| settings.background = settings.background.cycle(step),
+    },
+    OptionEntry {
+        label: "Zen mode",
+        value: |settings| on_off(settings.zen_mode),
+        change: |settings, _| settings.zen_mode = !settings.zen_mode,
+    },
+    OptionEntry {
+        label: "Step by step (next run)",
+        value: |settings| on_off(settings.step_mode),
+        change: |settings, _| settings.step_mode = !settings.step_mode,
+    },
+    OptionEntry {
+        label: "Risk mode (next run)",
+        value: |settings| on_off(settings.risk_mode),
+        change: |settings, _| settings.risk_mode = !settings.risk_mode,
+    },
+    OptionEntry {
+        label: "Pause in background",
+        value: |settings| on_off(settings.pause_in_background),
+        change: |settings, _| settings.pause_in_background = !settings.pause_in_background,
+    },
+    OptionEntry {
+        label: "Anti-aliasing",
+        value: |settings| match settings.anti_aliasing {
+            AntiAliasing::Off => String::from("Off"),
+            AntiAliasing::Msaa2 => String::from("2x"),
+            AntiAliasing::Msaa4 => String::from("4x"),
+        },
+        change: |settings, step| settings.anti_aliasing = settings.anti_aliasing.cycle(step),
+    },
+    OptionEntry {
+        label: "Frame rate",
+        value: |settings| match settings.frame_sync {
+            FrameSync::VSync => String::from("VSync"),
+            FrameSync::Unlimited => String::from("Unlimited"),
+        },
+        change: |settings, _| {
+            settings.frame_sync = match settings.frame_sync {
+                FrameSync::VSync => FrameSync::Unlimited,
+                FrameSync::Unlimited => FrameSync::VSync,
+            }
+        },
+    },
+    OptionEntry {
+        label: "Body gradient",
+        value: |settings| on_off(settings.body_gradient),
+        change: |settings, _| settings.body_gradient = !settings.body_gradient,
+    },
+    OptionEntry {
+        label: "Length tiers",
+        value: |settings| match settings.length_tiers {
+            Some(length) => format!("Every {length}"),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.length_tiers = match settings.length_tiers.unwrap_or(0) as i32 + step * 5 {
+                ..=0 => None,
+                length => Some((length as u32).min(50)),
+            }
+        },
+    },
+    OptionEntry {
+        label: "CRT effect",
+        value: |settings| on_off(settings.crt_effect),
+        change: |settings, _| settings.crt_effect = !settings.crt_effect,
+    },
+    OptionEntry {
+        label: "Font",
+        value: |settings| match &settings.font {
+            Some(name) => name.clone(),
+            None => String::from("Built-in"),
+        },
+        change: |settings, step| {
+            let mut choices = vec![None];
+            choices.extend(list_custom_fonts().into_iter().map(Some));
+            let index = choices
+                .iter()
+                .position(|choice| *choice == settings.font)
+                .unwrap_or(0) as i32;
+            settings.font =
+                choices[(index + step).rem_euclid(choices.len() as i32) as usize].clone();
+        },
+    },
+    OptionEntry {
+        label: "Auto-save",
+        value: |settings| match settings.auto_save {
+            Some(interval) => format!("every {interval}s"),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.auto_save = match settings.auto_save.unwrap_or(0.0) + step as f32 * 10.0 {
+                ..=0.0 => None,
+                interval => Some(interval.min(300.0)),
+            }
+        },
+    },
+    OptionEntry {
+        label: "Player one input",
+        value: |settings| format!("{:?}", settings.player_one_input),
+        change: |settings, step| settings.player_one_input = settings.player_one_input.cycle(step),
+    },
+    OptionEntry {
+        label: "Ghost of best run (next run)",
+        value: |settings| on_off(settings.show_ghost),
+        change: |settings, _| settings.show_ghost = !settings.show_ghost,
+    },
+    OptionEntry {
+        label: "Wall pulse",
+        value: |settings| on_off(settings.wall_pulse),
+        change: |settings, _| settings.wall_pulse = !settings.wall_pulse,
+    },
+    OptionEntry {
+        label: "Wrap flash",
+        value: |settings| on_off(settings.wrap_flash),
+        change: |settings, _| settings.wrap_flash = !settings.wrap_flash,
+    },
+    OptionEntry {
+        label: "Intro animation",
+        value: |settings| on_off(settings.intro_animation),
+        change: |settings, _| settings.intro_animation = !settings.intro_animation,
+    },
+    OptionEntry {
+        label: "Digestion",
+        value: |settings| on_off(settings.show_digestion),
+        change: |settings, _| settings.show_digestion = !settings.show_digestion,
+    },
+    OptionEntry {
+        label: "Worm motion",
+        value: |settings| on_off(settings.worm_motion),
+        change: |settings, _| settings.worm_motion = !settings.worm_motion,
+    },
+    OptionEntry {
+        label: "Monochrome (next run)",
+        value: |settings| on_off(settings.monochrome),
+        change: |settings, _| settings.monochrome = !settings.monochrome,
+    },
+    OptionEntry {
+        label: "Follow camera",
+        value: |settings| on_off(settings.follow_camera),
+        change: |settings, _| settings.follow_camera = !settings.follow_camera,
+    },
+    OptionEntry {
+        label: "Zoom",
+        value: |settings| format!("{}x", settings.camera_zoom),
+        change: |settings, step| {
+            settings.camera_zoom =
+                (settings.camera_zoom + step as f32 * ZOOM_STEP).clamp(MIN_ZOOM, MAX_ZOOM)
+        },
+    },
+    OptionEntry {
+        label: "Speedrun timer",
+        value: |settings| on_off(settings.show_speedrun_timer),
+        change: |settings, _| settings.show_speedrun_timer = !settings.show_speedrun_timer,
+    },
+    OptionEntry {
+        label: "Board size (next run)",
+        value: |settings| format!("{0}x{0}", settings.board_size),
+        change: |settings, step| {
+            settings.board_size = (settings.board_size + step * 2).clamp(7, 17)
+        },
+    },
+    OptionEntry {
+        label: "Start direction (next run)",
+        value: |settings| format!("{:?}", settings.start_direction),
+        change: |settings, step| {
+            const DIRECTIONS: [Direction; 4] = [
+                Direction::Up,
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+            ];
+            let index = DIRECTIONS
+                .iter()
+                .position(|direction| *direction == settings.start_direction)
+                .unwrap_or(0) as i32;
+            settings.start_direction =
+                DIRECTIONS[(index + step).rem_euclid(DIRECTIONS.len() as i32) as usize].clone();
+        },
+    },
+    OptionEntry {
+        label: "Solid walls (next run)",
+        value: |settings| on_off(settings.solid_walls),
+        change: |settings, _| settings.solid_walls = !settings.solid_walls,
+    },
+    OptionEntry {
+        label: "Tunnel walls (next run)",
+        value: |settings| on_off(settings.tunnel_walls),
+        change: |settings, _| settings.tunnel_walls = !settings.tunnel_walls,
+    },
+    OptionEntry {
+        label: "Wall grace",
+        value: |settings| match settings.wall_grace {
+            0 => String::from("Off"),
+            bounces => format!("{bounces} bounces"),
+        },
+        change: |settings, step| {
+            settings.wall_grace = (settings.wall_grace as i32 + step).clamp(0, 5) as u32
+        },
+    },
+    OptionEntry {
+        label: "Mirror mode (next run)",
+        value: |settings| on_off(settings.mirror_mode),
+        change: |settings, _| settings.mirror_mode = !settings.mirror_mode,
+    },
+    OptionEntry {
+        label: "Scenario (next run)",
+        value: |settings| match &settings.scenario {
+            Some(name) => name.clone(),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            let mut choices = vec![None];
+            choices.extend(list_scenarios().into_iter().map(Some));
+            let index = choices
+                .iter()
+                .position(|choice| *choice == settings.scenario)
+                .unwrap_or(0) as i32;
+            settings.scenario =
+                choices[(index + step).rem_euclid(choices.len() as i32) as usize].clone();
+        },
+    },
+    OptionEntry {
+        label: "Speed",
+        value: |settings| format!("{}ms", settings.movement_interval),
+        change: |settings, step| {
+            settings.movement_interval =
+                (settings.movement_interval as i64 - step as i64 * 10).clamp(50, 300) as u64
+        },
+    },
+    OptionEntry {
+        label: "Slow start",
+        value: |settings| match settings.start_ramp {
+            0.0 => String::from("Off"),
+            seconds => format!("{seconds}s"),
+        },
+        change: |settings, step| {
+            settings.start_ramp = (settings.start_ramp + step as f32).clamp(0.0, 10.0)
+        },
+    },
+    OptionEntry {
+        label: "Spawn protection (next run)",
+        value: |settings| match settings.spawn_invulnerability {
+            0.0 => String::from("Off"),
+            seconds => format!("{seconds}s"),
+        },
+        change: |settings, step| {
+            settings.spawn_invulnerability =
+                (settings.spawn_invulnerability + step as f32 * 0.5).clamp(0.0, 3.0)
+        },
+    },
+    OptionEntry {
+        label: "Start grace (next run)",
+        value: |settings| match settings.start_grace {
+            0.0 => String::from("Off"),
+            seconds => format!("{seconds}s"),
+        },
+        change: |settings, step| {
+            settings.start_grace = (settings.start_grace + step as f32).clamp(0.0, 10.0)
+        },
+    },
+    OptionEntry {
+        label: "Game over input delay",
+        value: |settings| format!("{}ms", settings.game_over_delay),
+        change: |settings, step| {
+            settings.game_over_delay =
+                (settings.game_over_delay as i64 + step as i64 * 100).clamp(0, 2000) as u64
+        },
+    },
+    OptionEntry {
+        label: "Path trail",
+        value: |settings| on_off(settings.show_trail),
+        change: |settings, _| settings.show_trail = !settings.show_trail,
+    },
+    OptionEntry {
+        label: "Apple placement",
+        value: |settings| format!("{:?}", settings.apple_placement),
+        change: |settings, step| settings.apple_placement = settings.apple_placement.cycle(step),
+    },
+    OptionEntry {
+        label: "Apple points",
+        value: |settings| settings.apple_value.to_string(),
+        change: |settings, step| {
+            settings.apple_value = settings
+                .apple_value
+                .saturating_add_signed(step)
+                .clamp(1, 10)
+        },
+    },
+    OptionEntry {
+        label: "Frenzy apple points",
+        value: |settings| settings.frenzy_apple_value.to_string(),
+        change: |settings, step| {
+            settings.frenzy_apple_value = settings
+                .frenzy_apple_value
+                .saturating_add_signed(step)
+                .clamp(1, 10)
+        },
+    },
+    OptionEntry {
+        label: "Apple hint",
+        value: |settings| format!("{:?}", settings.apple_hint),
+        change: |settings, step| settings.apple_hint = settings.apple_hint.cycle(step),
+    },
+    OptionEntry {
+        label: "Score style",
+        value: |settings| format!("{:?}", settings.score_style),
+        change: |settings, step| settings.score_style = settings.score_style.cycle(step),
+    },
+    OptionEntry {
+        label: "HUD layout",
+        value: |settings| format!("{:?}", settings.hud_layout),
+        change: |settings, step| settings.hud_layout = settings.hud_layout.cycle(step),
+    },
+    OptionEntry {
+        label: "Apple timeout",
+        value: |settings| match settings.apple_timeout {
+            Some(seconds) => format!("{seconds}s"),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.apple_timeout = match settings.apple_timeout.unwrap_or(0.0) + step as f32 {
+                ..=0.0 => None,
+                seconds => Some(seconds.min(30.0)),
+            }
+        },
+    },
+    OptionEntry {
+        label: "Danger zone",
+        value: |settings| match settings.danger_zone {
+            Some(cells) => format!("{cells} cells"),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.danger_zone = match settings.danger_zone.unwrap_or(0) as i32 + step {
+                ..=0 => None,
+                cells => Some(cells.min(8) as u32),
+            }
+        },
+    },
+    OptionEntry {
+        label: "Swipe threshold",
+        value: |settings| format!("{}px", settings.swipe_threshold),
+        change: |settings, step| {
+            settings.swipe_threshold = (settings.swipe_threshold + step as f32 * 10.0).max(10.0)
+        },
+    },
+    OptionEntry {
+        label: "One turn per step",
+        value: |settings| on_off(settings.strict_turns),
+        change: |settings, _| settings.strict_turns = !settings.strict_turns,
+    },
+    OptionEntry {
+        label: "Simultaneous keys",
+        value: |settings| format!("{:?}", settings.turn_priority),
+        change: |settings, step| settings.turn_priority = settings.turn_priority.cycle(step),
+    },
+    OptionEntry {
+        label: "Show blocked reversals",
+        value: |settings| on_off(settings.blocked_turn_feedback),
+        change: |settings, _| settings.blocked_turn_feedback = !settings.blocked_turn_feedback,
+    },
+    OptionEntry {
+        label: "Shrink apples",
+        value: |settings| format!("{:.0}%", settings.shrink_apple_chance * 100.0),
+        change: |settings, step| {
+            settings.shrink_apple_chance =
+                (settings.shrink_apple_chance + step as f64 * 0.05).clamp(0.0, 1.0)
+        },
+    },
+    OptionEntry {
+        label: "Hazard apples",
+        value: |settings| format!("{:.0}%", settings.hazard_apple_chance * 100.0),
+        change: |settings, step| {
+            settings.hazard_apple_chance =
+                (settings.hazard_apple_chance + step as f64 * 0.05).clamp(0.0, 1.0)
+        },
+    },
+    OptionEntry {
+        label: "Hazard penalty",
+        value: |settings| format!("{:?}", settings.hazard_penalty),
+        change: |settings, step| settings.hazard_penalty = settings.hazard_penalty.cycle(step),
+    },
+    OptionEntry {
+        label: "Path hints",
+        value: |settings| format!("{:.0}%", settings.path_hint_chance * 100.0),
+        change: |settings, step| {
+            settings.path_hint_chance =
+                (settings.path_hint_chance + step as f64 * 0.05).clamp(0.0, 1.0)
+        },
+    },
+    OptionEntry {
+        label: "Shields",
+        value: |settings| format!("{:.0}%", settings.shield_chance * 100.0),
+        change: |settings, step| {
+            settings.shield_chance = (settings.shield_chance + step as f64 * 0.05).clamp(0.0, 1.0)
+        },
+    },
+    OptionEntry {
+        label: "Path hint duration",
+        value: |settings| format!("{}s", settings.path_hint_duration),
+        change: |settings, step| {
+            settings.path_hint_duration =
+                (settings.path_hint_duration + step as f32).clamp(1.0, 15.0)
+        },
+    },
+    OptionEntry {
+        label: "Frenzy every",
+        value: |settings| match settings.frenzy_interval {
+            Some(interval) => format!("{interval}s"),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.frenzy_interval =
+                match settings.frenzy_interval.unwrap_or(0.0) + step as f32 * 10.0 {
+                    ..=0.0 => None,
+                    interval => Some(interval.min(120.0)),
+                }
+        },
+    },
+    OptionEntry {
+        label: "Frenzy duration",
+        value: |settings| format!("{}s", settings.frenzy_duration),
+        change: |settings, step| {
+            settings.frenzy_duration = (settings.frenzy_duration + step as f32).clamp(1.0, 15.0)
+        },
+    },
+    OptionEntry {
+        label: "Frenzy apples",
+        value: |settings| settings.frenzy_apples.to_string(),
+        change: |settings, step| {
+            settings.frenzy_apples = settings
+                .frenzy_apples
+                .saturating_add_signed(step)
+                .clamp(1, 20)
+        },
+    },
+    OptionEntry {
+        label: "Closing walls",
+        value: |settings| match settings.closing_walls {
+            Some(interval) => format!("every {interval}s"),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.closing_walls = match settings.closing_walls.unwrap_or(0.0) + step as f32 * 5.0
+            {
+                ..=0.0 => None,
+                interval => Some(interval.min(120.0)),
+            }
+        },
+    },
+    OptionEntry {
+        label: "Closing walls rows",
+        value: |settings| settings.closing_walls_rows.to_string(),
+        change: |settings, step| {
+            settings.closing_walls_rows = (settings.closing_walls_rows + step).clamp(1, 3)
+        },
+    },
+    OptionEntry {
+        label: "Max length",
+        value: |settings| match settings.max_length {
+            Some(length) => length.to_string(),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.max_length = match settings.max_length.unwrap_or(0) as i32 + step * 5 {
+                ..=0 => None,
+                length => Some((length as u32).min(200)),
+            }
+        },
+    },
+    OptionEntry {
+        label: "Growth per apple",
+        value: |settings| settings.growth_per_apple.to_string(),
+        change: |settings, step| {
+            settings.growth_per_apple = (settings.growth_per_apple as i32 + step).clamp(1, 5) as u32
+        },
+    },
+    OptionEntry {
+        label: "Growth delay",
+        value: |settings| match settings.growth_delay {
+            0 => String::from("Off"),
+            steps => format!("{steps} steps"),
+        },
+        change: |settings, step| {
+            settings.growth_delay = (settings.growth_delay as i32 + step).clamp(0, 10) as u32
+        },
+    },
+    OptionEntry {
+        label: "Max combo",
+        value: |settings| match settings.max_combo {
+            1 => String::from("Off"),
+            max_combo => format!("x{max_combo}"),
+        },
+        change: |settings, step| {
+            settings.max_combo = (settings.max_combo as i32 + step).clamp(1, 10) as u32
+        },
+    },
+    OptionEntry {
+        label: "Combo window",
+        value: |settings| format!("{}s", settings.combo_window),
+        change: |settings, step| {
+            settings.combo_window = (settings.combo_window + step as f32 * 0.5).clamp(0.5, 10.0)
+        },
+    },
+    OptionEntry {
+        label: "Short route bonus",
+        value: |settings| on_off(settings.route_bonus),
+        change: |settings, _| settings.route_bonus = !settings.route_bonus,
+    },
+    OptionEntry {
+        label: "Apples to win (next run)",
+        value: |settings| match settings.apple_target {
+            Some(apples) => apples.to_string(),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.apple_target = match settings.apple_target.unwrap_or(0) as i32 + step * 5 {
+                ..=0 => None,
+                apples => Some(apples.min(100) as u32),
+            }
+        },
+    },
+    OptionEntry {
+        label: "Sudden death (next run)",
+        value: |settings| match settings.sudden_death {
+            Some(seconds) => format!("{seconds}s"),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.sudden_death = match settings.sudden_death.unwrap_or(0.0) + step as f32 * 10.0
+            {
+                ..=0.0 => None,
+                seconds => Some(seconds.min(120.0)),
+            }
+        },
+    },
+    OptionEntry {
+        label: "Time per apple",
+        value: |settings| format!("{}s", settings.sudden_death_bonus),
+        change: |settings, step| {
+            settings.sudden_death_bonus =
+                (settings.sudden_death_bonus + step as f32).clamp(1.0, 15.0)
+        },
+    },
+    OptionEntry {
+        label: "Wrap limit",
+        value: |settings| match settings.wrap_limit {
+            Some(limit) => limit.to_string(),
+            None => String::from("Off"),
+        },
+        change: |settings, step| {
+            settings.wrap_limit = match settings.wrap_limit.unwrap_or(0) as i32 + step {
+                ..=0 => None,
+                limit => Some(limit as u32),
+            }
+        },
+    },
+];
+
+/// Rows below the options, one per action to rebind its key and one to restore the defaults.
+const KEY_ROWS: usize = Action::ALL.len() + 1;
+
+fn on_off(enabled: bool) -> String {
+    String::from(if enabled { "On" } else { "Off" })
+}
+
+#[derive(Component)]
+pub struct OptionsMenu;
+
+#[derive(Component)]
+pub struct OptionsText;
+
+#[derive(Resource, Default)]
+pub struct OptionsCursor(usize);
+
+/// Rebinding of a key from the options menu.
+#[derive(Resource, Default)]
+pub struct KeyRemap {
+    /// Action waiting for its new key to be pressed.
+    listening: Option<Action>,
+    /// Outcome of the last rebinding, like why a key was rejected.
+    message: Option<String>,
+}
+
+pub fn open_options(keys: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if keys.just_pressed(KeyCode::KeyO) {
+        next_state.set(GameState::Options);
+    }
+}
+
+pub fn spawn_options_menu(
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+    mut remap: ResMut<KeyRemap>,
+) {
+    *remap = KeyRemap::default();
+    commands
+        .spawn((
+            OptionsMenu,
+            Mesh2d(meshes.add(Rectangle::from_size(window.single().resolution.size()))),
+            MeshMaterial2d(color_materials.add(Color::srgba(0.0, 0.0, 0.0, 0.9))),
+            Transform::from_xyz(0.0, 0.0, 5.0),
+        ))
+        .with_child((
+            OptionsText,
+            Text2d::default(),
+            Transform::from_xyz(0.0, 0.0, 1.0),
+            TextColor(Color::srgb(0.5, 1.0, 1.0)),
+            TextFont {
+                font: game_font.handle.clone(),
+                font_size: FONT_SIZE,
+                ..default()
+            },
+        ));
+}
+
+pub fn navigate_options(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut cursor: ResMut<OptionsCursor>,
+    mut settings: ResMut<Settings>,
+    mut remap: ResMut<KeyRemap>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if let Some(action) = remap.listening {
+        capture_key(action, &keys, &mut remap, &mut settings);
+        return;
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Paused);
+        return;
+    }
+
+    let rows = OPTIONS.len() + KEY_ROWS;
+    if keys.any_just_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+        cursor.0 = (cursor.0 + rows - 1) % rows;
+    }
+    if keys.any_just_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+        cursor.0 = (cursor.0 + 1) % rows;
+    }
+
+    let mut step = 0;
+    if keys.any_just_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
+        step -= 1;
+    }
+    if keys.any_just_pressed([KeyCode::KeyD, KeyCode::ArrowRight, KeyCode::Enter]) {
+        step += 1;
+    }
+    if step == 0 {
+        return;
+    }
+    match cursor.0.checked_sub(OPTIONS.len()) {
+        None => (OPTIONS[cursor.0].change)(&mut settings, step),
+        Some(row) if row < Action::ALL.len() => {
+            remap.listening = Some(Action::ALL[row]);
+            remap.message = None;
+            return;
+        }
+        Some(_) => {
+            settings.key_bindings = KeyBindings::default();
+            remap.message = Some(String::from("Restored the default keys"));
+        }
+    }
+    save_settings(&settings).expect("could not save settings");
+}
+
+/// Binds the next pressed key to the action, or cancels with Esc. Keys that are taken or can't
+/// be bound are rejected with a message, leaving the old binding in place.
+fn capture_key(
+    action: Action,
+    keys: &ButtonInput<KeyCode>,
+    remap: &mut KeyRemap,
+    settings: &mut Settings,
+) {
+    let Some(&key) = keys.get_just_pressed().next() else {
+        return;
+    };
+    remap.listening = None;
+    if key == KeyCode::Escape {
+        return;
+    }
+    remap.message = Some(match settings.key_bindings.assign(action, key) {
+        Ok(()) => {
+            save_settings(settings).expect("could not save settings");
+            format!("{action:?} is now bound to {}", key_name(key))
+        }
+        Err(err) => err.to_string(),
+    });
+}
+
+pub fn update_options_text(
+    mut query: Query<&mut Text2d, With<OptionsText>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    cursor: Res<OptionsCursor>,
+    settings: Res<Settings>,
+    remap: Res<KeyRemap>,
+) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+
+    let rows = OPTIONS
+        .iter()
+        .map(|option| format!("{}: {}", option.label, (option.value)(&settings)))
+        .chain(Action::ALL.into_iter().map(|action| {
+            let key = if remap.listening == Some(action) {
+                String::from("press a key, Esc to cancel")
+            } else {
+                key_name(settings.key_bindings.key(action))
+            };
+            format!("{action:?} key: {key}")
+        }))
+        .chain([String::from("Restore default keys")])
+        .collect::<Vec<_>>();
+
+    // Only show as many rows as fit into the window, scrolled so the cursor stays visible.
+    let available_lines = (window.single().resolution.height() / LINE_HEIGHT) as usize;
+    let visible = available_lines.saturating_sub(7).clamp(1, rows.len());
+    let first = cursor.0.saturating_sub(visible - 1);
+    let lines = rows
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(i, row)| {
+            let marker = if i == cursor.0 { ">" } else { " " };
+            format!("{marker} {row}")
+        })
+        .collect::<Vec<_>>();
+    let more_above = if first > 0 { "..." } else { "" };
+    let more_below = if first + visible < rows.len() {
+        "..."
+    } else {
+        ""
+    };
+    let message = remap.message.as_deref().unwrap_or_default();
+    text.0 = format!(
+        "Options\n{more_above}\n{}\n{more_below}\n{message}\nEsc: Back",
+        lines.join("\n")
+    );
+}
+
+pub fn update_clear_color(settings: Res<Settings>, mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = settings.clear_color();
+}
+
+pub fn update_present_mode(
+    settings: Res<Settings>,
+    mut window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window.get_single_mut() else {
+        return;
+    };
+    let present_mode = settings.frame_sync.present_mode();
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+pub fn update_msaa(settings: Res<Settings>, mut cameras: Query<&mut Msaa, With<Camera2d>>) {
+    for mut msaa in cameras.iter_mut() {
+        *msaa = settings.anti_aliasing.msaa();
+    }
+}
@@ -0,0 +1,146 @@
+use crate::board::Board;
+use crate::grid::{Cell, Occupancy};
+use crate::settings::Settings;
+use crate::{Apple, BodyPart, Custom, Direction, GameRng, Head, Obstacle, choose_apple_spawn_cell};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use rand::Rng;
+use std::collections::VecDeque;
+
+/// Pickup that shows a safe route to the apple for a while once eaten.
+#[derive(Component)]
+pub struct PathHintPickup;
+
+/// Time left to show the route to the apple, `None` while no hint is active.
+#[derive(Resource, Default)]
+pub struct PathHint(Option<Timer>);
+
+#[derive(Component)]
+pub struct PathHintMark;
+
+/// Shortest route from `from` to `to` that avoids `blocked`, excluding `from` itself, or `None`
+/// if the apple can't be reached.
+///
+/// A breadth-first search that wraps around the edges like the snake does, trying the
+/// directions in a fixed order so the same board always shows the same route.
+pub fn safe_path(
+    board: &Board,
+    from: Cell,
+    to: Cell,
+    blocked: &HashSet<Cell>,
+) -> Option<Vec<Cell>> {
+    let mut came_from = HashMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(cell) = queue.pop_front() {
+        if cell == to {
+            let mut path = vec![];
+            let mut current = to;
+            while current != from {
+                path.push(current);
+                current = came_from[&current];
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for direction in [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ] {
+            let next = board.wrap(Cell(cell.0 + direction.to_ivec2()));
+            if board.contains(next) && !blocked.contains(&next) && !came_from.contains_key(&next) {
+                came_from.insert(next, cell);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+pub fn spawn_path_hint_pickup(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    settings: Res<Settings>,
+    board: Res<Board>,
+    occupancy: Occupancy,
+    pickups: Query<(), With<PathHintPickup>>,
+) {
+    if !pickups.is_empty() || !rng.0.random_bool(settings.path_hint_chance) {
+        return;
+    }
+    let Some(spawn_cell) = choose_apple_spawn_cell(&board, &occupancy.cells(), &mut rng.0) else {
+        return;
+    };
+
+    commands.spawn((
+        Custom,
+        PathHintPickup,
+        spawn_cell,
+        Sprite::from_color(
+            Color::srgb(1.0, 0.85, 0.2),
+            Vec2::splat(board.cell_size * 0.4),
+        ),
+        Transform::from_translation(board.cell_to_world(spawn_cell).extend(-1.0))
+            .with_rotation(Quat::from_rotation_z(f32::to_radians(45.0))),
+    ));
+}
+
+pub fn eat_path_hint_pickup(
+    mut commands: Commands,
+    mut path_hint: ResMut<PathHint>,
+    head: Query<&Cell, With<Head>>,
+    pickups: Query<(Entity, &Cell), With<PathHintPickup>>,
+    settings: Res<Settings>,
+) {
+    let head = head.single();
+    for (pickup, cell) in pickups.iter() {
+        if cell == head {
+            commands.entity(pickup).despawn();
+            path_hint.0 = Some(Timer::from_seconds(
+                settings.path_hint_duration,
+                TimerMode::Once,
+            ));
+        }
+    }
+}
+
+/// Redraws the route from the head to the apple while the hint is active.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn update_path_hint(
+    mut commands: Commands,
+    mut path_hint: ResMut<PathHint>,
+    marks: Query<Entity, With<PathHintMark>>,
+    head: Query<&Cell, With<Head>>,
+    apple: Query<&Cell, With<Apple>>,
+    blocked: Query<&Cell, (Or<(With<BodyPart>, With<Obstacle>)>, Without<Head>)>,
+    board: Res<Board>,
+    time: Res<Time>,
+) {
+    for mark in marks.iter() {
+        commands.entity(mark).despawn();
+    }
+    let Some(timer) = path_hint.0.as_mut() else {
+        return;
+    };
+    if timer.tick(time.delta()).finished() {
+        path_hint.0 = None;
+        return;
+    }
+    let Ok(apple) = apple.get_single() else {
+        return;
+    };
+
+    let blocked = blocked.iter().copied().collect();
+    let path = safe_path(&board, *head.single(), *apple, &blocked).unwrap_or_default();
+    let size = board.cell_size;
+    // The last cell is the apple itself.
+    for cell in path.iter().rev().skip(1) {
+        commands.spawn((
+            Custom,
+            PathHintMark,
+            Sprite::from_color(Color::srgba(1.0, 0.85, 0.2, 0.35), Vec2::splat(size * 0.5)),
+            Transform::from_translation(board.cell_to_world(*cell).extend(-1.6)),
+        ));
+    }
+}
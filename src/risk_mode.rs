@@ -0,0 +1,32 @@
+use crate::settings::Settings;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Factor applied to the scores of a risk run.
+const SCORE_MULTIPLIER: u32 = 2;
+/// Factor applied to the movement interval of a risk run, the snake moves that much faster.
+const INTERVAL_FACTOR: f32 = 0.7;
+
+/// Whether the current run is a risk run, which doubles every score in exchange for a faster
+/// snake and solid walls. Fixed when the run starts.
+///
+/// The walls come from [`Board::from_settings`](crate::board::Board::from_settings), risk runs
+/// keep their own high score.
+#[derive(Resource)]
+pub struct RiskMode(pub bool);
+
+impl RiskMode {
+    /// Step duration the run moves at once any slow start is over.
+    pub fn base_interval(&self, settings: &Settings) -> Duration {
+        let interval = Duration::from_millis(settings.movement_interval);
+        if self.0 {
+            interval.mul_f32(INTERVAL_FACTOR)
+        } else {
+            interval
+        }
+    }
+
+    pub fn score_multiplier(&self) -> u32 {
+        if self.0 { SCORE_MULTIPLIER } else { 1 }
+    }
+}
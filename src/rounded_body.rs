@@ -0,0 +1,126 @@
+use crate::board::Board;
+use crate::settings::{BodyStyle, Settings, save_settings};
+use crate::{Body, BodyPart, Custom, NextBodyPart, Tail};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_asset::RenderAssetUsages;
+use std::f32::consts::TAU;
+
+const CIRCLE_RESOLUTION: u32 = 16;
+
+#[derive(Component)]
+pub struct RoundedBody;
+
+pub fn spawn_rounded_body(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    color_materials: &mut Assets<ColorMaterial>,
+) {
+    commands.spawn((
+        Custom,
+        RoundedBody,
+        Mesh2d(meshes.add(Circle::default())),
+        MeshMaterial2d(color_materials.add(Color::srgb(0.25, 0.45, 0.12))),
+        Transform::from_xyz(0.0, 0.0, -1.5),
+        Visibility::Hidden,
+    ));
+}
+
+pub fn toggle_body_style(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
+    if keys.just_pressed(KeyCode::KeyB) {
+        settings.body_style = match settings.body_style {
+            BodyStyle::Sprites => BodyStyle::Rounded,
+            BodyStyle::Rounded => BodyStyle::Sprites,
+        };
+        save_settings(&settings).expect("could not save settings");
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_rounded_body(
+    settings: Res<Settings>,
+    board: Res<Board>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut rounded_body: Query<(&Mesh2d, &mut Visibility), With<RoundedBody>>,
+    mut body_parts: Query<(&mut Visibility, Has<Body>), (With<BodyPart>, Without<RoundedBody>)>,
+    tail: Query<Entity, With<Tail>>,
+    parts: Query<(&Transform, &NextBodyPart)>,
+) {
+    let is_rounded = settings.body_style == BodyStyle::Rounded;
+    for (mut visibility, is_body) in body_parts.iter_mut() {
+        *visibility = if is_rounded && is_body {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+
+    let Ok((mesh, mut visibility)) = rounded_body.get_single_mut() else {
+        return;
+    };
+    if !is_rounded {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    *visibility = Visibility::Inherited;
+
+    let Ok(tail) = tail.get_single() else {
+        return;
+    };
+    let mut points = vec![];
+    let mut next = Some(tail);
+    while let Some(entity) = next {
+        let Ok((transform, next_part)) = parts.get(entity) else {
+            return;
+        };
+        points.push(transform.translation.truncate());
+        next = next_part.0;
+    }
+
+    let size = board.cell_size;
+    if let Some(mesh) = meshes.get_mut(&mesh.0) {
+        *mesh = build_path_mesh(&points, size * 0.3, size * 1.5);
+    }
+}
+
+/// Builds a continuous path through `points` with round joints, skipping links longer than
+/// `max_link` (which happens when the snake wraps around the board).
+fn build_path_mesh(points: &[Vec2], radius: f32, max_link: f32) -> Mesh {
+    let mut positions: Vec<[f32; 3]> = vec![];
+    let mut indices: Vec<u32> = vec![];
+
+    for point in points {
+        let center = positions.len() as u32;
+        positions.push([point.x, point.y, 0.0]);
+        for i in 0..CIRCLE_RESOLUTION {
+            let angle = i as f32 / CIRCLE_RESOLUTION as f32 * TAU;
+            let rim = point + Vec2::from_angle(angle) * radius;
+            positions.push([rim.x, rim.y, 0.0]);
+            indices.extend([
+                center,
+                center + 1 + i,
+                center + 1 + (i + 1) % CIRCLE_RESOLUTION,
+            ]);
+        }
+    }
+
+    for link in points.windows(2) {
+        let (from, to) = (link[0], link[1]);
+        if from.distance(to) > max_link {
+            continue;
+        }
+        let normal = (to - from).normalize_or_zero().perp() * radius;
+        let start = positions.len() as u32;
+        positions.extend(
+            [from + normal, from - normal, to - normal, to + normal].map(|p| [p.x, p.y, 0.0]),
+        );
+        indices.extend([start, start + 1, start + 2, start, start + 2, start + 3]);
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_indices(Indices::U32(indices))
+}
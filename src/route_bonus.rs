@@ -0,0 +1,78 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use bevy::prelude::*;
+
+/// Bonus for reaching an apple on a shortest route.
+const SHORTEST_ROUTE_BONUS: u32 = 2;
+/// Bonus for a route at most a quarter longer than the shortest one.
+const SHORT_ROUTE_BONUS: u32 = 1;
+const TEXT_SECONDS: f32 = 1.5;
+
+/// Steps taken since the last apple, to reward routes close to the shortest possible one.
+///
+/// The first apple of a run doesn't earn a bonus, as the route is measured between apples.
+#[derive(Resource, Default)]
+pub struct RouteBonus {
+    from: Option<Cell>,
+    steps: u32,
+    /// Last bonus and how long it's still shown.
+    shown: Option<(u32, Timer)>,
+}
+
+impl RouteBonus {
+    pub fn count_step(&mut self) {
+        self.steps += 1;
+    }
+
+    /// Registers an apple eaten at `cell` and returns the bonus points for the route to it.
+    pub fn register_apple(&mut self, cell: Cell, board: &Board, settings: &Settings) -> u32 {
+        let bonus = match self.from {
+            Some(from) if settings.route_bonus => {
+                route_bonus(board.distance(from, cell), self.steps)
+            }
+            _ => 0,
+        };
+        self.from = Some(cell);
+        self.steps = 0;
+        if bonus > 0 {
+            self.shown = Some((bonus, Timer::from_seconds(TEXT_SECONDS, TimerMode::Once)));
+        }
+        bonus
+    }
+}
+
+fn route_bonus(shortest: u32, steps: u32) -> u32 {
+    if steps <= shortest {
+        SHORTEST_ROUTE_BONUS
+    } else if steps * 4 <= shortest * 5 {
+        SHORT_ROUTE_BONUS
+    } else {
+        0
+    }
+}
+
+#[derive(Component)]
+pub struct RouteBonusText;
+
+pub fn count_route_step(mut route_bonus: ResMut<RouteBonus>) {
+    route_bonus.count_step();
+}
+
+pub fn update_route_bonus_text(
+    mut route_bonus: ResMut<RouteBonus>,
+    mut query: Query<(&mut Text, &mut Visibility), With<RouteBonusText>>,
+    time: Res<Time>,
+) {
+    let (mut text, mut visibility) = query.single_mut();
+    let Some((bonus, timer)) = route_bonus.shown.as_mut() else {
+        return;
+    };
+    if timer.tick(time.delta()).finished() {
+        route_bonus.shown = None;
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    text.0 = format!("Short route: +{bonus}");
+    *visibility = Visibility::Inherited;
+}
@@ -0,0 +1,256 @@
+use crate::board::Board;
+use crate::camera::hud_layer;
+use crate::files::list_files;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::storage::{SCENARIO_DIRECTORY, SaveStorage, scenarios};
+use crate::{Custom, Obstacle};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use bincode::error::DecodeError;
+use bincode::{Decode, Encode, config};
+use std::io::ErrorKind;
+use std::time::Duration;
+use std::{fmt, io};
+
+/// Scenario the editor works on.
+pub const EDITOR_SCENARIO: &str = "custom";
+const WARNING_DURATION: Duration = Duration::from_secs(4);
+
+/// Hand-made board layout, cells are stored as `(x, y)` pairs.
+#[derive(Encode, Decode, Debug, Default, Clone)]
+pub struct Scenario {
+    pub obstacles: Vec<(i32, i32)>,
+    pub apple: Option<(i32, i32)>,
+    /// Cells of the snake from tail to head, to practice a tricky situation. Empty to start the
+    /// usual way.
+    pub snake: Vec<(i32, i32)>,
+}
+
+/// Layout of scenarios saved before they could place the snake.
+#[derive(Decode)]
+struct ScenarioWithoutSnake {
+    obstacles: Vec<(i32, i32)>,
+    apple: Option<(i32, i32)>,
+}
+
+/// Reason a scenario can't be played on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioError {
+    /// The snake has a head but no tail.
+    SnakeTooShort,
+    /// A cell of the snake isn't on the board.
+    OffBoard(Cell),
+    /// Two consecutive cells of the snake aren't neighbors.
+    Disconnected(Cell, Cell),
+    /// A cell of the snake is taken twice, or by an obstacle or the apple.
+    Overlap(Cell),
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioError::SnakeTooShort => write!(f, "the snake needs a head and a tail"),
+            ScenarioError::OffBoard(cell) => {
+                write!(f, "the snake leaves the board at {}, {}", cell.x, cell.y)
+            }
+            ScenarioError::Disconnected(from, to) => write!(
+                f,
+                "the snake is torn apart between {}, {} and {}, {}",
+                from.x, from.y, to.x, to.y
+            ),
+            ScenarioError::Overlap(cell) => {
+                write!(f, "the snake overlaps something at {}, {}", cell.x, cell.y)
+            }
+        }
+    }
+}
+
+impl Scenario {
+    pub fn snake_cells(&self) -> impl Iterator<Item = Cell> {
+        self.snake.iter().map(|&(x, y)| Cell::new(x, y))
+    }
+
+    /// Checks that the snake, if any, is a chain of neighboring cells on the board that doesn't
+    /// overlap anything. Obstacles and the apple may be off the board, which is smaller than the
+    /// one the scenario was made on, they are just left out then.
+    pub fn validate(&self, board: &Board) -> Result<(), ScenarioError> {
+        if self.snake.is_empty() {
+            return Ok(());
+        }
+        if self.snake.len() < 2 {
+            return Err(ScenarioError::SnakeTooShort);
+        }
+        let mut taken = self.obstacle_cells().collect::<HashSet<_>>();
+        taken.extend(self.apple_cell());
+        let cells = self.snake_cells().collect::<Vec<_>>();
+        for (index, cell) in cells.iter().enumerate() {
+            if !board.contains(*cell) {
+                return Err(ScenarioError::OffBoard(*cell));
+            }
+            if !taken.insert(*cell) {
+                return Err(ScenarioError::Overlap(*cell));
+            }
+            if let Some(previous) = index.checked_sub(1).map(|previous| cells[previous])
+                && board.distance(previous, *cell) != 1
+            {
+                return Err(ScenarioError::Disconnected(previous, *cell));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn obstacle_cells(&self) -> impl Iterator<Item = Cell> {
+        self.obstacles.iter().map(|&(x, y)| Cell::new(x, y))
+    }
+
+    pub fn apple_cell(&self) -> Option<Cell> {
+        self.apple.map(|(x, y)| Cell::new(x, y))
+    }
+
+    pub fn toggle_obstacle(&mut self, cell: Cell) {
+        let cell = (cell.x, cell.y);
+        if let Some(index) = self.obstacles.iter().position(|c| *c == cell) {
+            self.obstacles.remove(index);
+        } else {
+            self.obstacles.push(cell);
+            if self.apple == Some(cell) {
+                self.apple = None;
+            }
+        }
+    }
+
+    pub fn toggle_apple(&mut self, cell: Cell) {
+        let cell = (cell.x, cell.y);
+        self.apple = if self.apple == Some(cell) {
+            None
+        } else {
+            self.obstacles.retain(|c| *c != cell);
+            Some(cell)
+        };
+    }
+}
+
+pub fn list_scenarios() -> Vec<String> {
+    list_files(SCENARIO_DIRECTORY, &[])
+}
+
+pub fn load_scenario(name: &str) -> io::Result<Scenario> {
+    let Some(content) = scenarios().read(name)? else {
+        return Ok(Scenario::default());
+    };
+    match bincode::decode_from_slice(&content, config::standard()) {
+        Ok((scenario, _)) => Ok(scenario),
+        Err(DecodeError::UnexpectedEnd { .. }) => {
+            bincode::decode_from_slice(&content, config::standard())
+                .map(|(scenario, _): (ScenarioWithoutSnake, _)| Scenario {
+                    obstacles: scenario.obstacles,
+                    apple: scenario.apple,
+                    snake: vec![],
+                })
+                .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+        }
+        Err(err) => Err(io::Error::new(ErrorKind::InvalidData, err)),
+    }
+}
+
+/// Scenario chosen in the settings, or an empty one if none is chosen or it can't be loaded.
+pub fn load_selected_scenario(settings: &Settings) -> Scenario {
+    match &settings.scenario {
+        Some(name) => load_scenario(name).unwrap_or_else(|err| {
+            warn!("could not load scenario {name}, starting without it: {err}");
+            Scenario::default()
+        }),
+        None => Scenario::default(),
+    }
+}
+
+pub fn save_scenario(name: &str, scenario: &Scenario) -> io::Result<()> {
+    let encoded =
+        bincode::encode_to_vec(scenario, config::standard()).expect("failed to encode scenario");
+    scenarios().write(name, &encoded)
+}
+
+/// Tells why the chosen scenario wasn't used for the run.
+#[derive(Component)]
+pub struct ScenarioWarning(Timer);
+
+pub fn spawn_scenario_warning(
+    commands: &mut Commands,
+    window: &Window,
+    font: Handle<Font>,
+    err: &ScenarioError,
+) {
+    commands.spawn((
+        Custom,
+        ScenarioWarning(Timer::new(WARNING_DURATION, TimerMode::Once)),
+        hud_layer(),
+        Text2d::new(format!("Scenario not loaded: {err}")),
+        TextColor(Color::srgb(1.0, 0.3, 0.2)),
+        TextFont {
+            font,
+            font_size: 30.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, window.resolution.height() / -2.0 + 70.0, 1.0),
+    ));
+}
+
+pub fn expire_scenario_warnings(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut ScenarioWarning)>,
+    time: Res<Time>,
+) {
+    for (entity, mut warning) in query.iter_mut() {
+        if warning.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn spawn_obstacle(commands: &mut Commands, cell: Cell, board: &Board) {
+    commands.spawn((
+        Custom,
+        Obstacle,
+        cell,
+        Sprite::from_color(Color::srgb(0.2, 0.25, 0.25), Vec2::splat(board.cell_size)),
+        Transform::from_translation(board.cell_to_world(cell).extend(-1.0)),
+    ));
+}
+
+/// Obstacles of the scenario that are on the board and don't block the given cells.
+pub fn placeable_obstacles<'a>(
+    scenario: &'a Scenario,
+    board: &'a Board,
+    blocked: &'a [Cell],
+) -> impl Iterator<Item = Cell> + 'a {
+    scenario
+        .obstacle_cells()
+        .filter(|cell| board.contains(*cell) && !blocked.contains(cell))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scenarios_round_trip_through_the_storage() {
+        let scenario = Scenario {
+            obstacles: vec![(1, 2), (-3, 0)],
+            apple: Some((4, 4)),
+            snake: vec![(0, 0), (1, 0)],
+        };
+        save_scenario("maze", &scenario).unwrap();
+
+        let loaded = load_scenario("maze").unwrap();
+        assert_eq!(loaded.obstacles, scenario.obstacles);
+        assert_eq!(loaded.apple, scenario.apple);
+        assert_eq!(loaded.snake, scenario.snake);
+    }
+
+    #[test]
+    fn missing_scenarios_load_empty() {
+        let scenario = load_scenario("missing").unwrap();
+        assert!(scenario.obstacles.is_empty() && scenario.apple.is_none());
+    }
+}
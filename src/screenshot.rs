@@ -0,0 +1,77 @@
+use crate::Custom;
+use crate::camera::hud_layer;
+use crate::fonts::GameFont;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured, save_to_disk};
+use bevy::window::PrimaryWindow;
+use std::path::Path;
+use std::time::Duration;
+
+const NOTICE_DURATION: Duration = Duration::from_secs(2);
+
+#[derive(Component)]
+pub struct ScreenshotNotice(Timer);
+
+/// Screenshots are numbered rather than timestamped, as the web has no system clock. Natively
+/// the number skips past screenshots of earlier sessions, while the browser downloads them.
+pub fn take_screenshot(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut number: Local<u32>,
+) {
+    if !keys.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    let path = Path::new("screenshots");
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Err(err) = std::fs::create_dir_all(path) {
+        error!("could not create screenshot directory: {err}");
+        return;
+    }
+    let file = loop {
+        *number += 1;
+        let file = path.join(format!("snake-{}.png", *number));
+        if !file.exists() {
+            break file;
+        }
+    };
+
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(file))
+        .observe(show_screenshot_notice);
+}
+
+fn show_screenshot_notice(
+    _trigger: Trigger<ScreenshotCaptured>,
+    mut commands: Commands,
+    window: Query<&Window, With<PrimaryWindow>>,
+    game_font: Res<GameFont>,
+) {
+    let resolution = &window.single().resolution;
+    commands.spawn((
+        Custom,
+        ScreenshotNotice(Timer::new(NOTICE_DURATION, TimerMode::Once)),
+        hud_layer(),
+        Text2d::new("Screenshot saved"),
+        TextFont {
+            font: game_font.handle.clone(),
+            font_size: 30.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, resolution.height() / -2.0 + 30.0, 1.0),
+    ));
+}
+
+pub fn expire_screenshot_notices(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut ScreenshotNotice)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut notice) in query.iter_mut() {
+        if notice.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
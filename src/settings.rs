@@ -0,0 +1,360 @@
+use crate::Direction;
+use crate::apple_hint::AppleHint;
+use crate::apple_pips::ScoreStyle;
+use crate::apple_placement::PlacementMode;
+use crate::hazard_apple::HazardPenalty;
+use crate::hud::HudLayout;
+use crate::input::{InputDevice, TurnPriority};
+use crate::key_bindings::KeyBindings;
+use crate::storage::{SaveStorage, saves};
+use bevy::prelude::*;
+use bevy::window::PresentMode;
+use bincode::{Decode, Encode, config};
+use std::io;
+
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BodyStyle {
+    #[default]
+    Sprites,
+    Rounded,
+}
+
+/// Color of the window around the board.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    #[default]
+    Charcoal,
+    Midnight,
+    Forest,
+    Sand,
+}
+
+impl Background {
+    const ALL: [Background; 4] = [
+        Background::Charcoal,
+        Background::Midnight,
+        Background::Forest,
+        Background::Sand,
+    ];
+
+    pub fn color(self) -> Color {
+        match self {
+            // Bevy's default clear color.
+            Background::Charcoal => Color::srgb_u8(43, 44, 47),
+            Background::Midnight => Color::srgb(0.05, 0.05, 0.15),
+            Background::Forest => Color::srgb(0.05, 0.2, 0.1),
+            Background::Sand => Color::srgb(0.6, 0.55, 0.4),
+        }
+    }
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|b| *b == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+/// Multisample anti-aliasing level, smoothing mostly the edges of the rounded body.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AntiAliasing {
+    Off,
+    Msaa2,
+    /// Bevy's default.
+    #[default]
+    Msaa4,
+}
+
+impl AntiAliasing {
+    const ALL: [AntiAliasing; 3] = [AntiAliasing::Off, AntiAliasing::Msaa2, AntiAliasing::Msaa4];
+
+    pub fn msaa(self) -> Msaa {
+        match self {
+            AntiAliasing::Off => Msaa::Off,
+            AntiAliasing::Msaa2 => Msaa::Sample2,
+            AntiAliasing::Msaa4 => Msaa::Sample4,
+        }
+    }
+
+    pub fn cycle(self, step: i32) -> Self {
+        let index = Self::ALL.iter().position(|a| *a == self).unwrap_or(0) as i32;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as i32) as usize]
+    }
+}
+
+/// How frames are presented to the screen.
+///
+/// Bevy's present modes are `AutoVsync` and `AutoNoVsync`, which pick the best supported mode,
+/// and the explicit `Fifo` (classic vsync), `FifoRelaxed` (vsync that tears when a frame is
+/// late), `Mailbox` (no tearing, lowest latency) and `Immediate` (no vsync at all). Only the
+/// automatic ones are offered, as they are supported everywhere.
+#[derive(Encode, Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FrameSync {
+    /// Caps the frame rate at the display's refresh rate, which saves power.
+    #[default]
+    VSync,
+    /// Renders as many frames as possible.
+    Unlimited,
+}
+
+impl FrameSync {
+    pub fn present_mode(self) -> PresentMode {
+        match self {
+            FrameSync::VSync => PresentMode::AutoVsync,
+            FrameSync::Unlimited => PresentMode::AutoNoVsync,
+        }
+    }
+}
+
+#[derive(Resource, Encode, Decode, Debug, Clone)]
+pub struct Settings {
+    pub body_style: BodyStyle,
+    pub background: Background,
+    /// Relaxed endless mode: no deaths or countdown, hidden score, soft colors and breathing apples.
+    pub zen_mode: bool,
+    /// Puzzle mode in which the snake moves one cell per pressed direction instead of on a
+    /// timer, takes effect on the next run.
+    pub step_mode: bool,
+    /// Doubles every score in exchange for a faster snake and solid walls, takes effect on the
+    /// next run.
+    pub risk_mode: bool,
+    /// Pauses the game when its window loses focus or the app is sent to the background.
+    pub pause_in_background: bool,
+    pub anti_aliasing: AntiAliasing,
+    pub frame_sync: FrameSync,
+    /// Fades the body sprites from a bright head toward a darker tail.
+    pub body_gradient: bool,
+    /// Number of parts after which the body shifts to the next color, `None` to keep its color.
+    pub length_tiers: Option<u32>,
+    /// Draws scanlines and a curved screen frame over the game like an old CRT monitor.
+    pub crt_effect: bool,
+    /// File name of a font in `assets/fonts/custom` to use for all texts, `None` for the
+    /// built-in font.
+    pub font: Option<String>,
+    /// Keys that steer the snake besides the arrow keys.
+    pub key_bindings: KeyBindings,
+    /// Device the first player steers with.
+    pub player_one_input: InputDevice,
+    /// Replays the best run so far as a translucent ghost to race against.
+    pub show_ghost: bool,
+    /// Briefly brightens the walls on every step.
+    pub wall_pulse: bool,
+    /// Flashes both edges of the board when the snake wraps around.
+    pub wrap_flash: bool,
+    /// Slides the snake onto the board before every run starts.
+    pub intro_animation: bool,
+    /// Tints the part that ate an apple, which then travels down the body as the snake moves on.
+    pub show_digestion: bool,
+    /// Lets the body trail behind the head on springs instead of sitting rigidly on the grid.
+    pub worm_motion: bool,
+    /// Draws the game in two colors with square snake parts, like on an old handheld.
+    pub monochrome: bool,
+    /// Shows how long the run takes to the hundredth of a second, stopping at the apple target.
+    pub show_speedrun_timer: bool,
+    /// Keeps the head in the center of the screen and scrolls the board instead.
+    pub follow_camera: bool,
+    /// Magnification of the board, above `1` zooms in.
+    pub camera_zoom: f32,
+    /// Number of cells along each side of the board, takes effect on the next run.
+    pub board_size: i32,
+    /// Direction the snake starts moving in, takes effect on the next run.
+    pub start_direction: Direction,
+    /// Adds a mirrored second snake that must never touch the first, takes effect on the next run.
+    pub mirror_mode: bool,
+    /// Walls end the run instead of wrapping around, takes effect on the next run.
+    pub solid_walls: bool,
+    /// Draws wrapping walls as dashed tunnels instead of solid lines, takes effect on the next run.
+    pub tunnel_walls: bool,
+    /// Number of times per run the snake bounces off a solid wall before hitting it ends the run.
+    pub wall_grace: u32,
+    /// Name of the scenario in `assets/scenarios` to start runs with, takes effect on the next run.
+    pub scenario: Option<String>,
+    /// Seconds between saves of the running game, `None` to not save it.
+    pub auto_save: Option<f32>,
+    /// Base time in milliseconds the snake needs to move one cell.
+    pub movement_interval: u64,
+    /// Seconds over which the snake speeds up from a slow start to the base speed, `0` to disable.
+    pub start_ramp: f32,
+    /// Seconds at the start of a run during which collisions don't end it.
+    pub spawn_invulnerability: f32,
+    /// Seconds the snake crawls at the start of a run until the first turn, `0` to disable.
+    pub start_grace: f32,
+    /// Milliseconds after a run ended during which keys don't leave the game over screen.
+    pub game_over_delay: u64,
+    /// Minimum drag distance in logical pixels before a mouse or touch swipe turns the snake.
+    pub swipe_threshold: f32,
+    /// Allows only one turn per step, ignoring further presses until the snake moved.
+    pub strict_turns: bool,
+    /// Which key wins if keys of both axes are pressed at once.
+    pub turn_priority: TurnPriority,
+    /// Flashes the head when a turn back into the neck is ignored.
+    pub blocked_turn_feedback: bool,
+    /// Probability that eating an apple also spawns a shrink apple, if none is on the board.
+    pub shrink_apple_chance: f64,
+    /// Probability that eating an apple also spawns a hazard apple, if none is on the board.
+    pub hazard_apple_chance: f64,
+    /// What eating a hazard apple does.
+    pub hazard_penalty: HazardPenalty,
+    /// Probability that eating an apple spawns a path hint pickup, if none is on the board.
+    pub path_hint_chance: f64,
+    /// Probability that eating an apple spawns a shield pickup, if none is on the board or held.
+    pub shield_chance: f64,
+    /// Seconds a path hint shows the way to the apple.
+    pub path_hint_duration: f32,
+    /// Seconds between two frenzies flooding the board with extra apples, `None` for no frenzies.
+    pub frenzy_interval: Option<f32>,
+    /// Seconds the extra apples of a frenzy stay on the board.
+    pub frenzy_duration: f32,
+    /// Number of extra apples spawned by a frenzy.
+    pub frenzy_apples: u32,
+    /// Seconds between two times the walls close in, `None` to keep the board as it is.
+    pub closing_walls: Option<f32>,
+    /// Rows the walls move inward on every side each time they close in.
+    pub closing_walls_rows: i32,
+    /// Length the snake stops growing at, `None` to let it grow without limit.
+    pub max_length: Option<u32>,
+    /// Number of segments the snake grows by for every apple.
+    pub growth_per_apple: u32,
+    /// Steps after eating an apple before the snake starts growing, `0` to grow right away.
+    pub growth_delay: u32,
+    /// Seconds after eating an apple in which the next one raises the combo multiplier.
+    pub combo_window: f32,
+    /// Highest combo multiplier, `1` disables combos.
+    pub max_combo: u32,
+    /// Awards bonus points for reaching an apple on a route close to the shortest one.
+    pub route_bonus: bool,
+    /// Apples to eat to win a run, `None` for endless runs, takes effect on the next run.
+    pub apple_target: Option<u32>,
+    /// Seconds on the clock at the start of a sudden death run, `None` to disable the mode.
+    pub sudden_death: Option<f32>,
+    /// Seconds every apple adds to the sudden death clock.
+    pub sudden_death_bonus: f32,
+    /// Where the regular apple tends to appear.
+    pub apple_placement: PlacementMode,
+    /// Points scored for a regular apple, before any multiplier.
+    pub apple_value: u32,
+    /// Points scored for an extra apple of a frenzy, before any multiplier.
+    pub frenzy_apple_value: u32,
+    /// Helps to spot the apple, for players who have a hard time finding it.
+    pub apple_hint: AppleHint,
+    /// Whether the score shows as text, as a row of apple pips or both.
+    pub score_style: ScoreStyle,
+    /// Whether the score readouts are stacked or squeezed onto one line for small windows.
+    pub hud_layout: HudLayout,
+    /// Seconds after which an uneaten apple moves to another cell, `None` to disable.
+    pub apple_timeout: Option<f32>,
+    /// Number of cells ahead of the head checked for imminent collisions, `None` to disable.
+    pub danger_zone: Option<u32>,
+    /// Highlights the cells the head visited this run with a fading trail.
+    pub show_trail: bool,
+    /// Maximum number of wraps within `wrap_window` before the snake speeds up, `None` to disable.
+    pub wrap_limit: Option<u32>,
+    /// Length of the sliding window in seconds used to count wraps for `wrap_limit`.
+    pub wrap_window: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            body_style: BodyStyle::default(),
+            background: Background::default(),
+            zen_mode: false,
+            step_mode: false,
+            risk_mode: false,
+            pause_in_background: false,
+            anti_aliasing: AntiAliasing::default(),
+            frame_sync: FrameSync::default(),
+            body_gradient: false,
+            length_tiers: None,
+            crt_effect: false,
+            monochrome: false,
+            wall_pulse: false,
+            wrap_flash: false,
+            intro_animation: false,
+            show_digestion: false,
+            worm_motion: false,
+            show_ghost: false,
+            key_bindings: KeyBindings::default(),
+            font: None,
+            player_one_input: InputDevice::default(),
+            show_speedrun_timer: false,
+            follow_camera: false,
+            camera_zoom: 1.0,
+            board_size: 13,
+            start_direction: Direction::default(),
+            mirror_mode: false,
+            solid_walls: false,
+            tunnel_walls: false,
+            wall_grace: 0,
+            scenario: None,
+            auto_save: None,
+            movement_interval: 100,
+            start_ramp: 0.0,
+            start_grace: 0.0,
+            game_over_delay: 500,
+            spawn_invulnerability: 0.5,
+            swipe_threshold: 30.0,
+            strict_turns: false,
+            turn_priority: TurnPriority::Turning,
+            blocked_turn_feedback: false,
+            shrink_apple_chance: 0.0,
+            hazard_apple_chance: 0.0,
+            hazard_penalty: HazardPenalty::EndRun,
+            path_hint_chance: 0.0,
+            shield_chance: 0.0,
+            path_hint_duration: 5.0,
+            frenzy_interval: None,
+            frenzy_duration: 5.0,
+            frenzy_apples: 5,
+            closing_walls: None,
+            closing_walls_rows: 1,
+            max_length: None,
+            growth_per_apple: 1,
+            growth_delay: 0,
+            combo_window: 3.0,
+            route_bonus: false,
+            max_combo: 1,
+            apple_target: None,
+            sudden_death: None,
+            sudden_death_bonus: 5.0,
+            apple_placement: PlacementMode::Uniform,
+            apple_value: 1,
+            frenzy_apple_value: 1,
+            apple_hint: AppleHint::Off,
+            score_style: ScoreStyle::Text,
+            hud_layout: HudLayout::Auto,
+            apple_timeout: None,
+            danger_zone: None,
+            show_trail: false,
+            wrap_limit: None,
+            wrap_window: 10.0,
+        }
+    }
+}
+
+impl Settings {
+    pub fn clear_color(&self) -> Color {
+        if self.zen_mode {
+            Color::srgb(0.55, 0.6, 0.7)
+        } else {
+            self.background.color()
+        }
+    }
+}
+
+pub fn load_settings() -> io::Result<Settings> {
+    let Some(content) = saves().read("settings")? else {
+        return Ok(Settings::default());
+    };
+    // Settings from an older version can't be decoded anymore, so start over with defaults.
+    Ok(bincode::decode_from_slice(&content, config::standard())
+        .map(|(settings, _)| settings)
+        .unwrap_or_else(|err| {
+            warn!("could not decode settings, falling back to defaults: {err}");
+            Settings::default()
+        }))
+}
+
+pub fn save_settings(settings: &Settings) -> io::Result<()> {
+    let encoded =
+        bincode::encode_to_vec(settings, config::standard()).expect("failed to encode settings");
+    saves().write("settings", &encoded)
+}
@@ -0,0 +1,94 @@
+use crate::board::Board;
+use crate::grid::{Cell, Occupancy};
+use crate::settings::Settings;
+use crate::{Custom, GameRng, Head, choose_apple_spawn_cell};
+use bevy::prelude::*;
+use rand::Rng;
+
+const SHIELD_COLOR: Color = Color::srgb(0.4, 0.8, 1.0);
+
+/// Pickup that grants a shield once eaten.
+#[derive(Component)]
+pub struct ShieldPickup;
+
+/// Whether the snake holds a shield, which negates the next lethal collision and is used up by
+/// it.
+#[derive(Resource, Default)]
+pub struct Shield(pub bool);
+
+/// Glow around the head while a shield is held.
+#[derive(Component)]
+pub struct ShieldAura;
+
+pub fn spawn_shield_aura(commands: &mut Commands, board: &Board) {
+    commands.spawn((
+        Custom,
+        ShieldAura,
+        Sprite::from_color(
+            SHIELD_COLOR.with_alpha(0.4),
+            Vec2::splat(board.cell_size * 1.4),
+        ),
+        Transform::from_xyz(0.0, 0.0, -0.5),
+        Visibility::Hidden,
+    ));
+}
+
+/// Spawns the pickup now and then, but never while one is on the board or a shield is held.
+pub fn spawn_shield_pickup(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    shield: Res<Shield>,
+    settings: Res<Settings>,
+    board: Res<Board>,
+    occupancy: Occupancy,
+    pickups: Query<(), With<ShieldPickup>>,
+) {
+    if shield.0 || !pickups.is_empty() || !rng.0.random_bool(settings.shield_chance) {
+        return;
+    }
+    let Some(spawn_cell) = choose_apple_spawn_cell(&board, &occupancy.cells(), &mut rng.0) else {
+        return;
+    };
+
+    commands.spawn((
+        Custom,
+        ShieldPickup,
+        spawn_cell,
+        Sprite::from_color(SHIELD_COLOR, Vec2::splat(board.cell_size * 0.5)),
+        Transform::from_translation(board.cell_to_world(spawn_cell).extend(-1.0)),
+    ));
+}
+
+pub fn eat_shield_pickup(
+    mut commands: Commands,
+    mut shield: ResMut<Shield>,
+    head: Query<&Cell, With<Head>>,
+    pickups: Query<(Entity, &Cell), With<ShieldPickup>>,
+) {
+    let head = head.single();
+    for (pickup, cell) in pickups.iter() {
+        if cell == head {
+            commands.entity(pickup).despawn();
+            shield.0 = true;
+        }
+    }
+}
+
+/// Keeps the aura on the drawn head, so it follows the head's motion too.
+pub fn update_shield_aura(
+    shield: Res<Shield>,
+    head: Query<&Transform, (With<Head>, Without<ShieldAura>)>,
+    mut aura: Query<(&mut Transform, &mut Visibility), With<ShieldAura>>,
+) {
+    let (Ok(head), Ok((mut transform, mut visibility))) =
+        (head.get_single(), aura.get_single_mut())
+    else {
+        return;
+    };
+    transform.translation = head.translation.truncate().extend(transform.translation.z);
+    *visibility = if shield.0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+}
@@ -0,0 +1,86 @@
+use crate::board::Board;
+use crate::grid::{Cell, Occupancy};
+use crate::settings::Settings;
+use crate::{
+    Body, BodyPart, Constants, Custom, GameRng, Head, NextBodyPart, SnakePart, Tail,
+    choose_apple_spawn_cell,
+};
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Number of tail segments removed when a shrink apple is eaten.
+const SHRINK_AMOUNT: usize = 2;
+/// Head, one body segment and tail.
+const MIN_LENGTH: usize = 3;
+
+#[derive(Component)]
+pub struct ShrinkApple;
+
+#[derive(Event)]
+pub struct ShrinkAppleEatenEvent(Entity);
+
+pub fn spawn_shrink_apple(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    settings: Res<Settings>,
+    constants: Res<Constants>,
+    board: Res<Board>,
+    occupancy: Occupancy,
+    shrink_apples: Query<(), With<ShrinkApple>>,
+) {
+    if !shrink_apples.is_empty() || !rng.0.random_bool(settings.shrink_apple_chance) {
+        return;
+    }
+
+    let Some(spawn_cell) = choose_apple_spawn_cell(&board, &occupancy.cells(), &mut rng.0) else {
+        return;
+    };
+
+    commands.spawn((
+        Custom,
+        ShrinkApple,
+        spawn_cell,
+        Sprite {
+            color: Color::srgb(0.4, 0.4, 1.0),
+            ..Sprite::from_image(constants.apple_texture_handle.clone())
+        },
+        Transform::from_translation(board.cell_to_world(spawn_cell).extend(-1.0)),
+    ));
+}
+
+pub fn eat_shrink_apple(
+    head_query: Query<&Cell, With<Head>>,
+    shrink_apple_query: Query<(Entity, &Cell), With<ShrinkApple>>,
+    mut shrink_apple_eaten_event: EventWriter<ShrinkAppleEatenEvent>,
+) {
+    let head_cell = head_query.single();
+    for (shrink_apple, cell) in shrink_apple_query.iter() {
+        if head_cell == cell {
+            shrink_apple_eaten_event.send(ShrinkAppleEatenEvent(shrink_apple));
+        }
+    }
+}
+
+pub fn shrink(
+    mut commands: Commands,
+    mut shrink_apple_eaten_event: EventReader<ShrinkAppleEatenEvent>,
+    constants: Res<Constants>,
+    tail_query: Query<Entity, With<Tail>>,
+    parts: Query<&NextBodyPart, With<BodyPart>>,
+) {
+    for shrink_apple in shrink_apple_eaten_event.read() {
+        commands.entity(shrink_apple.0).despawn();
+    }
+
+    let removable = parts.iter().count().saturating_sub(MIN_LENGTH);
+    let mut tail = tail_query.single();
+    for _ in 0..removable.min(SHRINK_AMOUNT) {
+        let next_part = parts.get(tail).expect("expected tail to be a body part");
+        commands.entity(tail).despawn();
+        tail = next_part.0.expect("expected tail to have a next_part");
+    }
+    commands
+        .entity(tail)
+        .remove::<Body>()
+        .insert((Tail, constants.part_sprite(SnakePart::Tail)));
+}
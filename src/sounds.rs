@@ -0,0 +1,26 @@
+use crate::files::list_files;
+use bevy::prelude::*;
+use std::path::Path;
+
+/// Directory relative to `assets` in which users can put their own crunch sounds, which replace
+/// the built-in ones if there is at least one.
+pub const CUSTOM_CRUNCH_DIRECTORY: &str = "sounds/crunch";
+const BUILT_IN_CRUNCH_COUNT: usize = 4;
+
+/// Crunch sounds to pick from when an apple is eaten, the custom ones if there are any and the
+/// built-in ones otherwise.
+pub fn load_crunch_sounds(asset_server: &AssetServer) -> Vec<Handle<AudioSource>> {
+    let custom = list_files(
+        Path::new("assets").join(CUSTOM_CRUNCH_DIRECTORY),
+        &["wav", "ogg"],
+    );
+    if custom.is_empty() {
+        return (1..=BUILT_IN_CRUNCH_COUNT)
+            .map(|i| asset_server.load(format!("sounds/apple-crunch-{i}.wav")))
+            .collect();
+    }
+    custom
+        .into_iter()
+        .map(|name| asset_server.load(format!("{CUSTOM_CRUNCH_DIRECTORY}/{name}")))
+        .collect()
+}
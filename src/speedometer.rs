@@ -0,0 +1,50 @@
+use crate::camera::hud_layer;
+use crate::{Custom, MovementTimer};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::WindowResolution;
+use std::time::Duration;
+
+/// Readout of the current speed in cells per second, holding the step duration it shows.
+#[derive(Component)]
+pub struct Speedometer(Duration);
+
+pub fn spawn_speedometer(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    resolution: &WindowResolution,
+) {
+    commands.spawn((
+        Custom,
+        Speedometer(Duration::ZERO),
+        hud_layer(),
+        Text2d::default(),
+        TextColor(Color::srgb(0.5, 1.0, 1.0)),
+        TextFont {
+            font,
+            font_size: 30.0,
+            ..default()
+        },
+        Anchor::TopRight,
+        Transform::from_translation(Vec3::new(
+            resolution.width() / 2.0 - 20.0,
+            resolution.height() / 2.0,
+            0.0,
+        )),
+    ));
+}
+
+/// Follows the movement timer, which ticks every frame, so the text is only rewritten when the
+/// step duration actually changed.
+pub fn update_speedometer(
+    movement_timer: Query<&MovementTimer>,
+    mut speedometer: Query<(&mut Speedometer, &mut Text2d)>,
+) {
+    let duration = movement_timer.single().0.duration();
+    let (mut speedometer, mut text) = speedometer.single_mut();
+    if speedometer.0 == duration {
+        return;
+    }
+    speedometer.0 = duration;
+    text.0 = format!("Speed: {:.1}/s", 1.0 / duration.as_secs_f32());
+}
@@ -0,0 +1,101 @@
+use crate::camera::hud_layer;
+use crate::objective::Objective;
+use crate::settings::Settings;
+use crate::{Custom, MovementEvent};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::WindowResolution;
+use std::time::Duration;
+
+/// Time of the current run to the hundredth of a second, from the first step until the
+/// objective is met.
+#[derive(Resource, Default)]
+pub struct SpeedrunTimer {
+    elapsed: Duration,
+    started: bool,
+}
+
+impl SpeedrunTimer {
+    /// Fixed width `mm:ss.hh`, so the digits don't jump around while the time runs.
+    fn format(&self) -> String {
+        let hundredths = self.elapsed.as_millis() / 10;
+        format!(
+            "{:02}:{:02}.{:02}",
+            hundredths / 6000,
+            hundredths / 100 % 60,
+            hundredths % 100
+        )
+    }
+}
+
+#[derive(Component)]
+pub struct SpeedrunText;
+
+pub fn spawn_speedrun_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    resolution: &WindowResolution,
+    settings: &Settings,
+) {
+    commands.spawn((
+        Custom,
+        SpeedrunText,
+        hud_layer(),
+        Text2d::new(SpeedrunTimer::default().format()),
+        TextColor(Color::srgb(0.5, 1.0, 1.0)),
+        TextFont {
+            font,
+            font_size: 30.0,
+            ..default()
+        },
+        Anchor::TopRight,
+        // Below the speedometer and the objective.
+        Transform::from_translation(Vec3::new(
+            resolution.width() / 2.0 - 20.0,
+            resolution.height() / 2.0 - 60.0,
+            0.0,
+        )),
+        speedrun_visibility(settings),
+    ));
+}
+
+pub fn tick_speedrun_timer(
+    mut timer: ResMut<SpeedrunTimer>,
+    mut movement_event: EventReader<MovementEvent>,
+    objective: Res<Objective>,
+    time: Res<Time>,
+) {
+    if !timer.started {
+        timer.started = movement_event.read().next().is_some();
+        return;
+    }
+    if !objective.is_won() {
+        timer.elapsed += time.delta();
+    }
+}
+
+pub fn update_speedrun_text(
+    timer: Res<SpeedrunTimer>,
+    mut query: Query<&mut Text2d, With<SpeedrunText>>,
+) {
+    for mut text in query.iter_mut() {
+        text.0 = timer.format();
+    }
+}
+
+pub fn update_speedrun_visibility(
+    settings: Res<Settings>,
+    mut query: Query<&mut Visibility, With<SpeedrunText>>,
+) {
+    for mut visibility in query.iter_mut() {
+        *visibility = speedrun_visibility(&settings);
+    }
+}
+
+fn speedrun_visibility(settings: &Settings) -> Visibility {
+    if settings.show_speedrun_timer {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
@@ -0,0 +1,102 @@
+use crate::camera::hud_layer;
+use crate::risk_mode::RiskMode;
+use crate::settings::Settings;
+use crate::{Custom, Direction, LastDirection, MovementTimer};
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::window::WindowResolution;
+use std::time::Duration;
+
+/// How much slower than the base interval the snake moves during the grace period.
+const GRACE_SLOWDOWN: u32 = 3;
+
+/// Time left to get oriented at the start of a run, during which the snake crawls at a fixed
+/// slow speed. `None` if the grace is disabled or already over.
+///
+/// Unlike the gradual [`StartRamp`](crate::ease_in::StartRamp), the grace ends at once, as
+/// soon as the player turns for the first time or the time runs out.
+#[derive(Resource)]
+pub struct StartGrace(Option<Timer>);
+
+impl StartGrace {
+    pub fn new(settings: &Settings) -> Self {
+        Self((settings.start_grace > 0.0).then(|| {
+            Timer::new(
+                Duration::from_secs_f32(settings.start_grace),
+                TimerMode::Once,
+            )
+        }))
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+#[derive(Component)]
+pub struct StartGraceText;
+
+pub fn spawn_start_grace_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    resolution: &WindowResolution,
+) {
+    commands.spawn((
+        Custom,
+        StartGraceText,
+        hud_layer(),
+        Text2d::default(),
+        TextColor(Color::srgb(0.5, 1.0, 1.0)),
+        TextFont {
+            font,
+            font_size: 30.0,
+            ..default()
+        },
+        Anchor::BottomCenter,
+        Transform::from_translation(Vec3::new(0.0, resolution.height() / -2.0 + 50.0, 0.0)),
+        Visibility::Hidden,
+    ));
+}
+
+pub fn tick_start_grace(
+    mut start_grace: ResMut<StartGrace>,
+    mut movement_timer: Query<&mut MovementTimer>,
+    direction: Query<(&Direction, &LastDirection)>,
+    risk_mode: Res<RiskMode>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    let Some(grace) = start_grace.0.as_mut() else {
+        return;
+    };
+    grace.tick(time.delta());
+
+    let base = risk_mode.base_interval(&settings);
+    let (direction, last_direction) = direction.single();
+    if grace.finished() || *direction != last_direction.0 {
+        // Hand over to the base speed, or to the slow start if that is enabled too.
+        movement_timer.single_mut().0.set_duration(base);
+        start_grace.0 = None;
+    } else {
+        movement_timer
+            .single_mut()
+            .0
+            .set_duration(base * GRACE_SLOWDOWN);
+    }
+}
+
+pub fn update_start_grace_text(
+    start_grace: Res<StartGrace>,
+    mut query: Query<(&mut Text2d, &mut Visibility), With<StartGraceText>>,
+) {
+    let (mut text, mut visibility) = query.single_mut();
+    let Some(grace) = &start_grace.0 else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    text.0 = format!(
+        "Get oriented: {:.0}s, turn to start",
+        grace.remaining_secs().ceil()
+    );
+    *visibility = Visibility::Inherited;
+}
@@ -0,0 +1,114 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::high_scores::GameMode;
+use crate::storage::{SaveStorage, saves};
+use crate::{Apple, Direction, LastDirection, MovementTimer, NextBodyPart, Score, Seed, Tail};
+use bevy::prelude::*;
+use std::fmt::Write;
+
+/// Name of the save the dump is written to, `assets/saves/state_dump.txt` natively.
+const DUMP_NAME: &str = "state_dump.txt";
+
+/// Describes the current run as text to attach to bug reports.
+///
+/// The format is stable, new lines are only ever added at the end:
+///
+/// ```text
+/// snake state dump v1
+/// seed: 42
+/// mode: Endless
+/// score: 3
+/// interval: 100ms
+/// direction: Right
+/// board: 15x15 Wrap
+/// apples: 2,-4
+/// snake: -2,0 -1,0 0,0
+/// ```
+///
+/// Cells are `x,y` pairs with the center cell at `0,0`, separated by spaces. The snake is listed
+/// from tail to head, and the direction is the one the snake last moved in.
+pub fn describe_state(world: &mut World) -> Result<String, String> {
+    let snake = snake_cells(world)?;
+    let apples = world
+        .query_filtered::<&Cell, With<Apple>>()
+        .iter(world)
+        .copied()
+        .collect::<Vec<_>>();
+    let score = world
+        .query::<&Score>()
+        .iter(world)
+        .next()
+        .map_or(0, |score| score.0);
+    let interval = world
+        .query::<&MovementTimer>()
+        .iter(world)
+        .next()
+        .map_or(0, |timer| timer.0.duration().as_millis());
+    let direction = world
+        .query::<&LastDirection>()
+        .iter(world)
+        .next()
+        .map_or(Direction::default(), |direction| direction.0.clone());
+    let board = world.get_resource::<Board>().ok_or("no run to dump")?;
+    let mode = world
+        .get_resource::<GameMode>()
+        .copied()
+        .unwrap_or_default();
+    let seed = world.resource::<Seed>().0;
+
+    let mut dump = String::from("snake state dump v1\n");
+    // Writing to a string can't fail.
+    let _ = writeln!(dump, "seed: {seed}");
+    let _ = writeln!(dump, "mode: {mode:?}");
+    let _ = writeln!(dump, "score: {score}");
+    let _ = writeln!(dump, "interval: {interval}ms");
+    let _ = writeln!(dump, "direction: {direction:?}");
+    let _ = writeln!(
+        dump,
+        "board: {0}x{0} {1:?}",
+        board.extent(),
+        board.wall_mode
+    );
+    let _ = writeln!(dump, "apples: {}", format_cells(&apples));
+    let _ = writeln!(dump, "snake: {}", format_cells(&snake));
+    Ok(dump)
+}
+
+/// Cells of the snake from tail to head, following the chain of parts.
+fn snake_cells(world: &mut World) -> Result<Vec<Cell>, String> {
+    let mut part = world
+        .query_filtered::<Entity, With<Tail>>()
+        .iter(world)
+        .next()
+        .ok_or("no snake to dump")?;
+    let mut cells = vec![];
+    let mut parts = world.query::<(&Cell, &NextBodyPart)>();
+    loop {
+        let (cell, next) = parts
+            .get(world, part)
+            .map_err(|err| format!("broken snake: {err}"))?;
+        cells.push(*cell);
+        match next.0 {
+            Some(next) => part = next,
+            None => return Ok(cells),
+        }
+    }
+}
+
+fn format_cells(cells: &[Cell]) -> String {
+    cells
+        .iter()
+        .map(|cell| format!("{},{}", cell.x, cell.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Writes the state of the run to the saves and the log.
+pub fn dump_state(world: &mut World) -> Result<String, String> {
+    let dump = describe_state(world)?;
+    info!("game state:\n{dump}");
+    saves()
+        .write(DUMP_NAME, dump.as_bytes())
+        .map_err(|err| format!("Could not write the dump: {err}"))?;
+    Ok(format!("Wrote the game state to {DUMP_NAME} and the log"))
+}
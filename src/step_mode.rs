@@ -0,0 +1,38 @@
+use crate::input::{first_gamepad, pressed_direction};
+use crate::settings::Settings;
+use crate::{LastDirection, MovementEvent};
+use bevy::prelude::*;
+
+/// Whether the snake of the current run only moves when the player presses a direction, one
+/// cell per press, instead of on a timer. Fixed when the run starts.
+///
+/// Every move can be thought through, like in a puzzle. Timers like the sudden death clock and
+/// frenzies still run in real time.
+#[derive(Resource)]
+pub struct StepMode(pub bool);
+
+pub fn is_step_mode(step_mode: Res<StepMode>) -> bool {
+    step_mode.0
+}
+
+/// Moves the snake one cell for every press of a direction, after `change_direction` turned it.
+/// A reversal into the neck is ignored as usual and doesn't move the snake either.
+pub fn step_on_input(
+    mut movement_event: EventWriter<MovementEvent>,
+    last_direction: Query<&LastDirection>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    settings: Res<Settings>,
+) {
+    let pressed_direction = pressed_direction(
+        settings.player_one_input,
+        &keys,
+        &settings.key_bindings,
+        first_gamepad(&gamepads),
+    );
+    if pressed_direction != IVec2::ZERO
+        && pressed_direction != -last_direction.single().0.to_ivec2()
+    {
+        movement_event.send(MovementEvent);
+    }
+}
@@ -0,0 +1,148 @@
+use std::io;
+#[cfg(test)]
+use std::{cell::RefCell, collections::HashMap};
+#[cfg(all(not(target_arch = "wasm32"), not(test)))]
+use std::{fs, path::Path};
+
+/// Persistence for saves like settings and high scores, files under `assets` natively and the
+/// browser's local storage on the web, where there is no file system.
+///
+/// A save that doesn't exist yet reads as `None` on both backends. Decoding is left to the
+/// callers, which fall back to defaults if a save is corrupt.
+pub trait SaveStorage {
+    fn read(&self, name: &str) -> io::Result<Option<Vec<u8>>>;
+    fn write(&self, name: &str, content: &[u8]) -> io::Result<()>;
+}
+
+/// Directory of the scenarios on native builds.
+pub const SCENARIO_DIRECTORY: &str = "assets/scenarios";
+
+/// The storage of the platform the game runs on.
+#[cfg(all(not(target_arch = "wasm32"), not(test)))]
+pub fn saves() -> impl SaveStorage {
+    FileStorage("assets/saves")
+}
+
+/// Storage of the scenarios made in the editor, kept apart from the other saves.
+#[cfg(all(not(target_arch = "wasm32"), not(test)))]
+pub fn scenarios() -> impl SaveStorage {
+    FileStorage(SCENARIO_DIRECTORY)
+}
+
+/// Tests keep their saves in memory, so they never touch the player's saves.
+#[cfg(test)]
+pub fn saves() -> impl SaveStorage {
+    MemoryStorage("")
+}
+
+#[cfg(test)]
+pub fn scenarios() -> impl SaveStorage {
+    MemoryStorage("scenarios/")
+}
+
+#[cfg(all(target_arch = "wasm32", not(test)))]
+pub fn saves() -> impl SaveStorage {
+    WebStorage("snake/")
+}
+
+#[cfg(all(target_arch = "wasm32", not(test)))]
+pub fn scenarios() -> impl SaveStorage {
+    WebStorage("snake/scenarios/")
+}
+
+/// Files in the directory.
+#[cfg(all(not(target_arch = "wasm32"), not(test)))]
+struct FileStorage(&'static str);
+
+#[cfg(all(not(target_arch = "wasm32"), not(test)))]
+impl SaveStorage for FileStorage {
+    fn read(&self, name: &str) -> io::Result<Option<Vec<u8>>> {
+        match fs::read(Path::new(self.0).join(name)) {
+            Ok(content) => Ok(Some(content)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Writes next to the save first and renames over it, so a crash while writing can't
+    /// leave a half written save behind.
+    fn write(&self, name: &str, content: &[u8]) -> io::Result<()> {
+        let path = Path::new(self.0);
+        fs::create_dir_all(path)?;
+        let temporary = path.join(format!("{name}.tmp"));
+        fs::write(&temporary, content)?;
+        fs::rename(temporary, path.join(name))
+    }
+}
+
+/// Saves kept per thread, so tests running side by side don't see each other's saves. Names
+/// start with the prefix to keep the storages apart.
+#[cfg(test)]
+struct MemoryStorage(&'static str);
+
+#[cfg(test)]
+thread_local! {
+    static MEMORY_SAVES: RefCell<HashMap<String, Vec<u8>>> = RefCell::default();
+}
+
+#[cfg(test)]
+impl SaveStorage for MemoryStorage {
+    fn read(&self, name: &str) -> io::Result<Option<Vec<u8>>> {
+        let key = format!("{}{name}", self.0);
+        Ok(MEMORY_SAVES.with_borrow(|saves| saves.get(&key).cloned()))
+    }
+
+    fn write(&self, name: &str, content: &[u8]) -> io::Result<()> {
+        let key = format!("{}{name}", self.0);
+        MEMORY_SAVES.with_borrow_mut(|saves| saves.insert(key, content.to_vec()));
+        Ok(())
+    }
+}
+
+/// Local storage only holds strings, so saves are stored hex encoded. Keys start with the
+/// prefix, as the local storage is shared by everything on the same origin.
+#[cfg(target_arch = "wasm32")]
+struct WebStorage(&'static str);
+
+#[cfg(target_arch = "wasm32")]
+impl WebStorage {
+    fn local_storage() -> io::Result<web_sys::Storage> {
+        web_sys::window()
+            .ok_or_else(|| io::Error::other("no window"))?
+            .local_storage()
+            .map_err(|err| io::Error::other(format!("{err:?}")))?
+            .ok_or_else(|| io::Error::other("local storage is unavailable"))
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl SaveStorage for WebStorage {
+    fn read(&self, name: &str) -> io::Result<Option<Vec<u8>>> {
+        let Some(hex) = Self::local_storage()?
+            .get_item(&format!("{}{name}", self.0))
+            .map_err(|err| io::Error::other(format!("{err:?}")))?
+        else {
+            return Ok(None);
+        };
+        // A broken encoding is corrupt just like broken content, decoding it fails right after.
+        let content = (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                hex.get(i..i + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        Ok(Some(content))
+    }
+
+    fn write(&self, name: &str, content: &[u8]) -> io::Result<()> {
+        let hex = content
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        Self::local_storage()?
+            .set_item(&format!("{}{name}", self.0), &hex)
+            .map_err(|err| io::Error::other(format!("{err:?}")))
+    }
+}
@@ -0,0 +1,55 @@
+use crate::GameOverEvent;
+use crate::settings::Settings;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Time left in sudden death mode before the run ends; `None` if the mode is disabled.
+#[derive(Resource)]
+pub struct SuddenDeath(Option<Duration>);
+
+impl SuddenDeath {
+    pub fn new(settings: &Settings) -> Self {
+        let start = settings.sudden_death.filter(|_| !settings.zen_mode);
+        Self(start.map(Duration::from_secs_f32))
+    }
+}
+
+#[derive(Component)]
+pub struct SuddenDeathText;
+
+pub fn count_down_sudden_death(
+    mut sudden_death: ResMut<SuddenDeath>,
+    mut game_over_event: EventWriter<GameOverEvent>,
+    time: Res<Time>,
+) {
+    let Some(remaining) = sudden_death.0.as_mut() else {
+        return;
+    };
+    if remaining.is_zero() {
+        return;
+    }
+    *remaining = remaining.saturating_sub(time.delta());
+    if remaining.is_zero() {
+        game_over_event.send(GameOverEvent);
+    }
+}
+
+pub fn extend_sudden_death(mut sudden_death: ResMut<SuddenDeath>, settings: Res<Settings>) {
+    if let Some(remaining) = sudden_death.0.as_mut() {
+        *remaining += Duration::from_secs_f32(settings.sudden_death_bonus);
+    }
+}
+
+pub fn update_sudden_death_text(
+    sudden_death: Res<SuddenDeath>,
+    mut query: Query<(&mut Text2d, &mut Visibility), With<SuddenDeathText>>,
+) {
+    let (mut text, mut visibility) = query.single_mut();
+    match sudden_death.0 {
+        Some(remaining) => {
+            text.0 = format!("{:.1}", remaining.as_secs_f32());
+            *visibility = Visibility::Inherited;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+}
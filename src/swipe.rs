@@ -0,0 +1,60 @@
+use crate::blocked_turn::BlockedTurnEvent;
+use crate::settings::Settings;
+use crate::{Direction, LastDirection, steer};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+/// Cursor position where the current mouse drag started.
+#[derive(Resource, Default)]
+pub struct SwipeStart(Option<Vec2>);
+
+pub fn steer_by_swipe(
+    mut query: Query<(&mut Direction, &LastDirection)>,
+    mut blocked_turn_event: EventWriter<BlockedTurnEvent>,
+    mut swipe_start: ResMut<SwipeStart>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<Settings>,
+) {
+    let mut swipes = touches
+        .iter_just_released()
+        .map(|touch| touch.distance())
+        .collect::<Vec<_>>();
+
+    let (Ok(window), Ok((mut direction, last_direction))) =
+        (window.get_single(), query.get_single_mut())
+    else {
+        return;
+    };
+    let cursor_position = window.cursor_position();
+    if mouse.just_pressed(MouseButton::Left) {
+        swipe_start.0 = cursor_position;
+    }
+    if mouse.just_released(MouseButton::Left)
+        && let (Some(start), Some(end)) = (swipe_start.0.take(), cursor_position)
+    {
+        swipes.push(end - start);
+    }
+
+    for swipe in swipes {
+        if swipe.length() < settings.swipe_threshold {
+            continue;
+        }
+        // Window coordinates grow downwards, so the y-axis is flipped compared to the world.
+        let pressed_direction = if swipe.x.abs() > swipe.y.abs() {
+            IVec2::new(swipe.x.signum() as i32, 0)
+        } else {
+            IVec2::new(0, -swipe.y.signum() as i32)
+        };
+        if steer(
+            &mut direction,
+            last_direction,
+            pressed_direction,
+            settings.strict_turns,
+            settings.turn_priority,
+        ) {
+            blocked_turn_event.send(BlockedTurnEvent);
+        }
+    }
+}
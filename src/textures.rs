@@ -0,0 +1,60 @@
+use crate::Constants;
+use bevy::asset::AssetLoadFailedEvent;
+use bevy::prelude::*;
+use std::path::Path;
+
+/// Directory relative to `assets` in which users can replace the built-in textures by adding
+/// images with the same file name.
+pub const CUSTOM_TEXTURE_DIRECTORY: &str = "textures/custom";
+
+/// Loads the custom version of the texture `name` if there is one, the built-in one otherwise.
+pub fn load_texture(asset_server: &AssetServer, name: &str) -> Handle<Image> {
+    let custom = format!("{CUSTOM_TEXTURE_DIRECTORY}/{name}");
+    if Path::new("assets").join(&custom).is_file() {
+        let handle = asset_server.load(custom);
+        // Known broken images stay failed, so don't wait for another failure event.
+        if !asset_server.load_state(&handle).is_failed() {
+            return handle;
+        }
+    }
+    asset_server.load(format!("textures/{name}"))
+}
+
+/// Replaces custom textures that couldn't be loaded with the built-in ones, both for new and
+/// for already spawned sprites.
+pub fn fall_back_to_builtin_textures(
+    mut events: EventReader<AssetLoadFailedEvent<Image>>,
+    mut constants: ResMut<Constants>,
+    mut sprites: Query<&mut Sprite>,
+    asset_server: Res<AssetServer>,
+) {
+    for event in events.read() {
+        let path = event.path.path();
+        if !path.starts_with(CUSTOM_TEXTURE_DIRECTORY) {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        warn!(
+            "could not load custom texture {path:?}, using the built-in one: {}",
+            event.error
+        );
+        let fallback: Handle<Image> = asset_server.load(format!("textures/{name}"));
+
+        let constants = constants.as_mut();
+        for handle in constants.snake_texture_handles.values_mut().chain([
+            &mut constants.apple_texture_handle,
+            &mut constants.hazard_apple_texture_handle,
+        ]) {
+            if handle.id() == event.id {
+                *handle = fallback.clone();
+            }
+        }
+        for mut sprite in sprites.iter_mut() {
+            if sprite.image.id() == event.id {
+                sprite.image = fallback.clone();
+            }
+        }
+    }
+}
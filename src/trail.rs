@@ -0,0 +1,58 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::{Custom, Head};
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Maximum number of visited cells kept, to bound memory on long runs.
+const TRAIL_CAPACITY: usize = 256;
+const TRAIL_ALPHA: f32 = 0.5;
+
+/// Highlights of the cells the head visited this run, oldest first.
+#[derive(Resource, Default)]
+pub struct Trail(VecDeque<Entity>);
+
+#[derive(Component)]
+pub struct TrailMark;
+
+pub fn update_trail(
+    mut commands: Commands,
+    mut trail: ResMut<Trail>,
+    mut marks: Query<&mut Sprite, With<TrailMark>>,
+    head: Query<&Cell, With<Head>>,
+    settings: Res<Settings>,
+    board: Res<Board>,
+) {
+    if !settings.show_trail {
+        for mark in trail.0.drain(..) {
+            commands.entity(mark).despawn();
+        }
+        return;
+    }
+
+    let size = board.cell_size;
+    let head_cell = head.single();
+    trail.0.push_back(
+        commands
+            .spawn((
+                Custom,
+                TrailMark,
+                Sprite::from_color(Color::NONE, Vec2::splat(size)),
+                Transform::from_translation(board.cell_to_world(*head_cell).extend(-1.8)),
+            ))
+            .id(),
+    );
+    if trail.0.len() > TRAIL_CAPACITY {
+        let oldest = trail.0.pop_front().expect("expected trail mark");
+        commands.entity(oldest).despawn();
+    }
+
+    let len = trail.0.len();
+    for (age, mark) in trail.0.iter().rev().enumerate() {
+        if let Ok(mut sprite) = marks.get_mut(*mark) {
+            let alpha = TRAIL_ALPHA * (1.0 - age as f32 / len as f32);
+            sprite.color = Color::srgba(1.0, 1.0, 0.6, alpha);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::{
+    Apple, AppleValue, Body, BodyPart, Constants, Custom, Direction, Head, LastDirection,
+    NextBodyPart, Tail, spawn_apple_at,
+};
+use bevy::prelude::*;
+
+#[derive(Clone, Copy)]
+enum PartKind {
+    Head,
+    Body,
+    Tail,
+}
+
+struct PartSnapshot {
+    kind: PartKind,
+    cell: Cell,
+    transform: Transform,
+    sprite: Sprite,
+}
+
+/// State of the snake and apple right before a step, from tail to head.
+struct StepSnapshot {
+    parts: Vec<PartSnapshot>,
+    apple: Option<Cell>,
+    direction: Direction,
+    last_direction: LastDirection,
+}
+
+/// The state before the last step, only one deep to bound memory.
+#[derive(Resource, Default)]
+pub struct UndoHistory(Option<StepSnapshot>);
+
+/// Undoing is a debugging aid to reproduce movement and collision issues step by step.
+pub fn is_debug_build() -> bool {
+    cfg!(debug_assertions)
+}
+
+#[allow(clippy::type_complexity)]
+pub fn record_step(
+    mut history: ResMut<UndoHistory>,
+    tail: Query<Entity, With<Tail>>,
+    parts: Query<(
+        &Cell,
+        &Transform,
+        &Sprite,
+        &NextBodyPart,
+        Has<Head>,
+        Has<Tail>,
+    )>,
+    apple: Query<&Cell, With<Apple>>,
+    direction: Query<(&Direction, &LastDirection)>,
+) {
+    let mut snapshots = vec![];
+    let mut next = Some(tail.single());
+    while let Some(entity) = next {
+        let (cell, transform, sprite, next_part, is_head, is_tail) =
+            parts.get(entity).expect("expected linked body part");
+        let kind = match (is_head, is_tail) {
+            (true, _) => PartKind::Head,
+            (_, true) => PartKind::Tail,
+            _ => PartKind::Body,
+        };
+        snapshots.push(PartSnapshot {
+            kind,
+            cell: *cell,
+            transform: *transform,
+            sprite: sprite.clone(),
+        });
+        next = next_part.0;
+    }
+
+    let (direction, last_direction) = direction.single();
+    history.0 = Some(StepSnapshot {
+        parts: snapshots,
+        apple: apple.get_single().ok().copied(),
+        direction: direction.clone(),
+        last_direction: last_direction.clone(),
+    });
+}
+
+/// Restores the snake and apple to their state before the last step when pressing U. The score
+/// is left as is.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn undo_step(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut history: ResMut<UndoHistory>,
+    current: Query<Entity, Or<(With<BodyPart>, With<Apple>)>>,
+    mut direction: Query<(&mut Direction, &mut LastDirection)>,
+    board: Res<Board>,
+    constants: Res<Constants>,
+    settings: Res<Settings>,
+) {
+    if !keys.just_pressed(KeyCode::KeyU) {
+        return;
+    }
+    let Some(snapshot) = history.0.take() else {
+        return;
+    };
+
+    for entity in current.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mut next = None;
+    for part in snapshot.parts.into_iter().rev() {
+        let mut entity = commands.spawn((
+            Custom,
+            BodyPart,
+            NextBodyPart(next),
+            part.cell,
+            part.transform,
+            part.sprite,
+        ));
+        match part.kind {
+            PartKind::Head => entity.insert(Head),
+            PartKind::Body => entity.insert(Body),
+            PartKind::Tail => entity.insert(Tail),
+        };
+        next = Some(entity.id());
+    }
+    if let Some(cell) = snapshot.apple {
+        spawn_apple_at(
+            &mut commands,
+            cell,
+            &board,
+            constants.apple_texture_handle.clone(),
+            AppleValue(settings.apple_value),
+        );
+    }
+
+    let (mut direction, mut last_direction) = direction.single_mut();
+    *direction = snapshot.direction;
+    *last_direction = snapshot.last_direction;
+}
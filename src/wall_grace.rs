@@ -0,0 +1,78 @@
+use crate::board::Board;
+use crate::camera::hud_layer;
+use crate::grid::Cell;
+use crate::{Custom, Direction};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use std::time::Duration;
+
+const WARNING_DURATION: Duration = Duration::from_millis(1500);
+
+/// Number of times the snake bounced off a solid wall this run.
+#[derive(Resource, Default)]
+pub struct WallHits(pub u32);
+
+#[derive(Component)]
+pub struct WallWarning(Timer);
+
+/// Picks a turn away from the wall after the head ran into it while moving in `last_direction`.
+///
+/// Reversing isn't allowed, so only the two perpendicular directions are candidates. Cells off
+/// the board rule a turn out, and a free cell is preferred over one blocked by the snake.
+pub fn bounce_direction(
+    board: &Board,
+    head: Cell,
+    last_direction: &Direction,
+    blocked: &HashSet<Cell>,
+) -> Option<Direction> {
+    let candidates = match last_direction {
+        Direction::Left | Direction::Right => [Direction::Up, Direction::Down],
+        Direction::Up | Direction::Down => [Direction::Left, Direction::Right],
+    };
+    let on_board = candidates
+        .into_iter()
+        .filter(|direction| board.contains(Cell(head.0 + direction.to_ivec2())))
+        .collect::<Vec<_>>();
+    on_board
+        .iter()
+        .find(|direction| !blocked.contains(&Cell(head.0 + direction.to_ivec2())))
+        .or(on_board.first())
+        .cloned()
+}
+
+pub fn spawn_wall_warning(
+    commands: &mut Commands,
+    window: &Window,
+    font: Handle<Font>,
+    hits_left: u32,
+) {
+    let text = match hits_left {
+        0 => String::from("Wall hit! Next one ends the run"),
+        _ => format!("Wall hit! {hits_left} bounces left"),
+    };
+    commands.spawn((
+        Custom,
+        WallWarning(Timer::new(WARNING_DURATION, TimerMode::Once)),
+        hud_layer(),
+        Text2d::new(text),
+        TextColor(Color::srgb(1.0, 0.3, 0.2)),
+        TextFont {
+            font,
+            font_size: 30.0,
+            ..default()
+        },
+        Transform::from_xyz(0.0, window.resolution.height() / -2.0 + 30.0, 1.0),
+    ));
+}
+
+pub fn expire_wall_warnings(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut WallWarning)>,
+    time: Res<Time>,
+) {
+    for (entity, mut warning) in query.iter_mut() {
+        if warning.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
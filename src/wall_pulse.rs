@@ -0,0 +1,37 @@
+use crate::MovementTimer;
+use crate::board::Wall;
+use crate::settings::Settings;
+use bevy::prelude::*;
+
+/// How much lighter the walls are at the start of a step.
+const PULSE_BRIGHTNESS: f32 = 0.15;
+
+/// Brightens the walls on every step and fades them back within the first half of it, so they
+/// beat faster as the snake speeds up.
+///
+/// Follows the movement timer instead of reacting to each step on its own, which restarts
+/// the pulse exactly when the snake moves.
+pub fn pulse_walls(
+    settings: Res<Settings>,
+    movement_timer: Query<&MovementTimer>,
+    walls: Query<(&Wall, &MeshMaterial2d<ColorMaterial>)>,
+    mut color_materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // Once turned off, the walls only need to be reset to their color once.
+    if !settings.wall_pulse && !settings.is_changed() {
+        return;
+    }
+    let pulse = if settings.wall_pulse {
+        let Ok(movement_timer) = movement_timer.get_single() else {
+            return;
+        };
+        (1.0 - 2.0 * movement_timer.0.fraction()).max(0.0)
+    } else {
+        0.0
+    };
+    for (wall, material) in walls.iter() {
+        if let Some(material) = color_materials.get_mut(&material.0) {
+            material.color = wall.color.lighter(PULSE_BRIGHTNESS * pulse);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::board::Board;
+use crate::grid::Cell;
+use crate::settings::Settings;
+use crate::{BodyPart, Head, NextBodyPart};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Stiffness of the spring pulling each part back onto its cell, per second squared.
+const STIFFNESS: f32 = 900.0;
+/// Critical damping for the stiffness, so parts settle within about a step without overshooting.
+const DAMPING: f32 = 60.0;
+/// Share of the offset of the part in front that each part is pulled along by.
+const PULL: f32 = 0.6;
+/// Largest offset of a part from its cell, in cells, which is where a new head starts.
+const MAX_OFFSET: f32 = 1.0;
+/// Longest time step of the spring, so a frame hitch can't make it blow up.
+const MAX_DELTA: f32 = 1.0 / 30.0;
+
+/// How far a part is drawn off its cell, and how fast that changes.
+///
+/// Only the drawn position lags behind, the [`Cell`] stays the part's position for collisions.
+#[derive(Component, Default)]
+pub struct WormSpring {
+    offset: Vec2,
+    velocity: Vec2,
+}
+
+/// Lets the body trail behind the head on springs, for a wormy look.
+///
+/// A new head starts where the old one was drawn and springs onto its cell. Every part is pulled
+/// along by the part in front of it, so each step sends a fading ripple down the body. Across a
+/// wrap, the new head starts on its cell, as sliding over from the other side of the board would
+/// cut through it.
+#[allow(clippy::type_complexity)]
+pub fn spring_body_parts(
+    mut commands: Commands,
+    mut parts: Query<
+        (
+            Entity,
+            &Cell,
+            &NextBodyPart,
+            &mut Transform,
+            Option<&mut WormSpring>,
+        ),
+        With<BodyPart>,
+    >,
+    new_heads: Query<(Entity, &Cell), Added<Head>>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    if !settings.worm_motion {
+        // Once turned off, the parts only need to be put back onto their cells once.
+        if settings.is_changed() {
+            for (entity, cell, _, mut transform, _) in parts.iter_mut() {
+                let position = board.cell_to_world(*cell);
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+                commands.entity(entity).remove::<WormSpring>();
+            }
+        }
+        return;
+    }
+
+    let offsets: HashMap<Entity, Vec2> = parts
+        .iter()
+        .map(|(entity, _, _, _, spring)| (entity, spring.map_or(Vec2::ZERO, |s| s.offset)))
+        .collect();
+    let mut new_head_offsets = HashMap::new();
+    for (head, head_cell) in new_heads.iter() {
+        let neck = parts.iter().find(|(_, _, next, _, _)| next.0 == Some(head));
+        if let Some((neck, neck_cell, _, _, _)) = neck
+            && (neck_cell.0 - head_cell.0).abs().element_sum() == 1
+        {
+            let offset =
+                board.cell_to_world(*neck_cell) - board.cell_to_world(*head_cell) + offsets[&neck];
+            new_head_offsets.insert(head, offset);
+        }
+    }
+
+    let delta = time.delta_secs().min(MAX_DELTA);
+    let max_offset = MAX_OFFSET * board.cell_size;
+    for (entity, cell, next, mut transform, spring) in parts.iter_mut() {
+        let Some(mut spring) = spring else {
+            let offset = new_head_offsets.get(&entity).copied().unwrap_or_default();
+            let position = board.cell_to_world(*cell) + offset;
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            commands.entity(entity).insert(WormSpring {
+                offset,
+                velocity: Vec2::ZERO,
+            });
+            continue;
+        };
+        let target = next
+            .0
+            .and_then(|next| offsets.get(&next))
+            .map_or(Vec2::ZERO, |offset| *offset * PULL);
+        let acceleration = (target - spring.offset) * STIFFNESS - spring.velocity * DAMPING;
+        spring.velocity += acceleration * delta;
+        spring.offset = (spring.offset + spring.velocity * delta).clamp_length_max(max_offset);
+
+        let position = board.cell_to_world(*cell) + spring.offset;
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
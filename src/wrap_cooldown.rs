@@ -0,0 +1,38 @@
+use crate::settings::Settings;
+use crate::{MovementTimer, WrapEvent};
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Speed-up applied to the movement timer each time the wrap limit is exceeded.
+const PENALTY_FACTOR: f32 = 0.9;
+const MIN_MOVEMENT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Times of the wraps within the current window, oldest first.
+#[derive(Resource, Default)]
+pub struct WrapCounter(Vec<Duration>);
+
+/// Speeds up the snake when it wraps around the board too often, to discourage hugging the edges.
+pub fn penalize_edge_camping(
+    mut wrap_event: EventReader<WrapEvent>,
+    mut wrap_counter: ResMut<WrapCounter>,
+    mut movement_timer: Query<&mut MovementTimer>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+) {
+    let wraps = wrap_event.read().count();
+    let Some(wrap_limit) = settings.wrap_limit else {
+        return;
+    };
+
+    let now = time.elapsed();
+    let window = Duration::from_secs_f32(settings.wrap_window);
+    wrap_counter.0.retain(|wrap| now - *wrap <= window);
+    wrap_counter.0.extend(std::iter::repeat_n(now, wraps));
+
+    if wrap_counter.0.len() > wrap_limit as usize {
+        wrap_counter.0.clear();
+        let mut timer = movement_timer.single_mut();
+        let duration = timer.0.duration().mul_f32(PENALTY_FACTOR);
+        timer.0.set_duration(duration.max(MIN_MOVEMENT_INTERVAL));
+    }
+}
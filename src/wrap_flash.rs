@@ -0,0 +1,55 @@
+use crate::board::Board;
+use crate::settings::Settings;
+use crate::{Custom, WrapEvent};
+use bevy::prelude::*;
+
+/// How long a flash takes to fade out.
+const FLASH_SECONDS: f32 = 0.3;
+const FLASH_COLOR: Color = Color::srgb(0.5, 1.0, 1.0);
+
+/// Glow on the edge of the board where the head left or came back in, fading until it is gone.
+#[derive(Component)]
+pub struct WrapFlash(Timer);
+
+/// Marks both edges of a wrap, so it is easier to follow where the snake went.
+pub fn spawn_wrap_flashes(
+    mut commands: Commands,
+    mut wrap_event: EventReader<WrapEvent>,
+    board: Res<Board>,
+    settings: Res<Settings>,
+) {
+    for wrap in wrap_event.read() {
+        if !settings.wrap_flash {
+            continue;
+        }
+        let direction = wrap.direction.as_vec2();
+        let half_step = direction * board.cell_size / 2.0;
+        // Thin along the direction of travel, as wide as a cell across it.
+        let size = Vec2::splat(board.cell_size) - direction.abs() * board.cell_size * 0.75;
+        for position in [
+            board.cell_to_world(wrap.exit) + half_step,
+            board.cell_to_world(wrap.entry) - half_step,
+        ] {
+            commands.spawn((
+                Custom,
+                WrapFlash(Timer::from_seconds(FLASH_SECONDS, TimerMode::Once)),
+                Sprite::from_color(FLASH_COLOR, size),
+                Transform::from_translation(position.extend(0.5)),
+            ));
+        }
+    }
+}
+
+pub fn fade_wrap_flashes(
+    mut commands: Commands,
+    mut flashes: Query<(Entity, &mut WrapFlash, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut sprite) in flashes.iter_mut() {
+        if flash.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        } else {
+            sprite.color.set_alpha(1.0 - flash.0.fraction());
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::apple_pips::ApplePips;
+use crate::settings::Settings;
+use crate::{Apple, HighScore, Score};
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+/// Pulses per second of the apples in zen mode.
+const PULSE_FREQUENCY: f32 = 0.5;
+const PULSE_AMPLITUDE: f32 = 0.08;
+
+/// Hides the score in zen mode, otherwise shows the parts the score style asks for.
+pub fn update_score_visibility(
+    settings: Res<Settings>,
+    mut score: Query<&mut Visibility, With<Score>>,
+    mut high_score: Query<&mut Visibility, (With<HighScore>, Without<Score>)>,
+    mut pips: Query<&mut Node, With<ApplePips>>,
+) {
+    let visible = |shown: bool| {
+        if shown && !settings.zen_mode {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        }
+    };
+    for mut visibility in score.iter_mut() {
+        *visibility = visible(settings.score_style.shows_text());
+    }
+    for mut visibility in high_score.iter_mut() {
+        *visibility = visible(true);
+    }
+    // Taken out of the layout rather than hidden, so the row leaves no gap behind.
+    for mut node in pips.iter_mut() {
+        node.display = match visible(settings.score_style.shows_pips()) {
+            Visibility::Hidden => Display::None,
+            _ => Display::Flex,
+        };
+    }
+}
+
+/// Lets the apples gently breathe in zen mode.
+pub fn animate_apples(
+    settings: Res<Settings>,
+    mut query: Query<&mut Transform, With<Apple>>,
+    time: Res<Time>,
+) {
+    let scale = if settings.zen_mode {
+        1.0 + PULSE_AMPLITUDE * (time.elapsed_secs() * PULSE_FREQUENCY * TAU).sin()
+    } else {
+        1.0
+    };
+    for mut transform in query.iter_mut() {
+        transform.scale = Vec3::splat(scale);
+    }
+}